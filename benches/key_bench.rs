@@ -0,0 +1,132 @@
+//! Time and heap allocations of common operations on short keys.
+//!
+//! Allocations are counted by a global allocator and printed before timing,
+//! Criterion refuses to analyze measurements of zero, which is the expected count here.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bisection_key::{BalancedKey, LexiconKey};
+use criterion::{criterion_group, Criterion};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ROUNDS: usize = 10_000;
+
+/// average number of heap allocations of one call
+fn allocations_of<T>(mut f: impl FnMut() -> T) -> f64 {
+  let start = ALLOCATIONS.load(Ordering::Relaxed);
+  for _ in 0..ROUNDS {
+    black_box(f());
+  }
+  (ALLOCATIONS.load(Ordering::Relaxed) - start) as f64 / ROUNDS as f64
+}
+
+fn report_allocations() {
+  let (a, b) = (
+    LexiconKey::new("aT3").unwrap(),
+    LexiconKey::new("aTx").unwrap(),
+  );
+  let (p, q) = (
+    BalancedKey::new("aT3").unwrap(),
+    BalancedKey::new("aTx").unwrap(),
+  );
+  let mut buf = String::with_capacity(64);
+
+  println!("heap allocations per call:");
+  let counts = [
+    ("lexicon bisect", allocations_of(|| a.bisect(&b))),
+    ("balanced bisect", allocations_of(|| p.bisect(&q))),
+    ("lexicon bisect_end", allocations_of(|| a.bisect_end())),
+    ("lexicon cmp", allocations_of(|| a.cmp(&b))),
+    ("balanced cmp", allocations_of(|| p.cmp(&q))),
+    (
+      "lexicon display",
+      allocations_of(|| {
+        buf.clear();
+        write!(buf, "{}", a)
+      }),
+    ),
+    (
+      "balanced display",
+      allocations_of(|| {
+        buf.clear();
+        write!(buf, "{}", p)
+      }),
+    ),
+  ];
+  for (name, count) in counts {
+    println!("  {:<20}{}", name, count);
+  }
+}
+
+fn operations(c: &mut Criterion) {
+  let (a, b) = (
+    LexiconKey::new("aT3").unwrap(),
+    LexiconKey::new("aTx").unwrap(),
+  );
+  let (p, q) = (
+    BalancedKey::new("aT3").unwrap(),
+    BalancedKey::new("aTx").unwrap(),
+  );
+  let mut buf = String::with_capacity(64);
+
+  c.bench_function("lexicon bisect", |bench| {
+    bench.iter(|| black_box(&a).bisect(black_box(&b)))
+  });
+  c.bench_function("balanced bisect", |bench| {
+    bench.iter(|| black_box(&p).bisect(black_box(&q)))
+  });
+  c.bench_function("lexicon bisect_end", |bench| {
+    bench.iter(|| black_box(&a).bisect_end())
+  });
+  c.bench_function("lexicon cmp", |bench| {
+    bench.iter(|| black_box(&a).cmp(black_box(&b)))
+  });
+  c.bench_function("balanced cmp", |bench| {
+    bench.iter(|| black_box(&p).cmp(black_box(&q)))
+  });
+  c.bench_function("lexicon display", |bench| {
+    bench.iter(|| {
+      buf.clear();
+      write!(buf, "{}", black_box(&a)).unwrap();
+    })
+  });
+  c.bench_function("balanced display", |bench| {
+    bench.iter(|| {
+      buf.clear();
+      write!(buf, "{}", black_box(&p)).unwrap();
+    })
+  });
+}
+
+criterion_group!(benches, operations);
+
+fn main() {
+  report_allocations();
+  benches();
+  Criterion::default().configure_from_args().final_summary();
+}
@@ -1,8 +1,8 @@
 extern crate bisection_key;
 
-use bisection_key::BalancedKey;
+use bisection_key::{BalancedKey, BisectionKeyError};
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), BisectionKeyError> {
   // let a = BalancedKey::new("aV")?;
   // let b = BalancedKey::new("b")?;
   // println!("{}", a.bisect(&b)?);
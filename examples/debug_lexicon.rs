@@ -1,8 +1,8 @@
 extern crate bisection_key;
 
-use bisection_key::LexiconKey;
+use bisection_key::{BisectionKeyError, LexiconKey};
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), BisectionKeyError> {
   // let a = LexiconKey::new("aV")?;
   // let b = LexiconKey::new("b")?;
   // println!("{}", a.bisect(&b)?);
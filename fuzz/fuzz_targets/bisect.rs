@@ -0,0 +1,28 @@
+//! bisecting any two keys either fails or gives a key strictly between them
+
+#![no_main]
+
+use bisection_key::{BalancedKey, Base94, FractionalIndexKey, FractionalKey, LexiconKey};
+use libfuzzer_sys::fuzz_target;
+
+fn check<K: FractionalKey>(a: &str, b: &str, bisect: impl Fn(&K, &K) -> Option<K>) {
+  let (Ok(a), Ok(b)) = (a.parse::<K>(), b.parse::<K>()) else {
+    return;
+  };
+  if let Some(mid) = bisect(&a, &b) {
+    let (lo, hi) = if a < b { (&a, &b) } else { (&b, &a) };
+    assert!(lo < &mid && &mid < hi, "{} < {} < {}", lo, mid, hi);
+    assert!(mid.to_string().parse::<K>().is_ok_and(|k| k == mid));
+  }
+}
+
+fuzz_target!(|input: (&str, &str)| {
+  let (a, b) = input;
+  check::<LexiconKey>(a, b, |a, b| a.bisect(b).ok());
+  check::<LexiconKey<Base94>>(a, b, |a, b| a.bisect(b).ok());
+  check::<BalancedKey>(a, b, |a, b| a.bisect(b).ok());
+  check::<BalancedKey<Base94>>(a, b, |a, b| a.bisect(b).ok());
+  check::<FractionalIndexKey>(a, b, |a, b| {
+    FractionalIndexKey::between(Some(a), Some(b)).ok()
+  });
+});
@@ -0,0 +1,22 @@
+//! a key after any key is larger than it
+
+#![no_main]
+
+use bisection_key::{BalancedKey, Base94, FractionalIndexKey, FractionalKey, LexiconKey};
+use libfuzzer_sys::fuzz_target;
+
+fn check<K: FractionalKey>(s: &str) {
+  if let Ok(k) = s.parse::<K>() {
+    if let Ok(next) = k.after() {
+      assert!(k < next, "{} < {}", k, next);
+    }
+  }
+}
+
+fuzz_target!(|s: &str| {
+  check::<LexiconKey>(s);
+  check::<LexiconKey<Base94>>(s);
+  check::<BalancedKey>(s);
+  check::<BalancedKey<Base94>>(s);
+  check::<FractionalIndexKey>(s);
+});
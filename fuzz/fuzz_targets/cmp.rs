@@ -0,0 +1,34 @@
+//! comparing keys is a total order, which matches the order of their ordered bytes
+
+#![no_main]
+
+use core::cmp::Ordering;
+
+use bisection_key::{BalancedKey, Base94, LexiconKey};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, &str)| {
+  let (a, b) = input;
+  if let (Ok(x), Ok(y)) = (a.parse::<LexiconKey>(), b.parse::<LexiconKey>()) {
+    assert_eq!(x.cmp(&y), y.cmp(&x).reverse());
+    assert_eq!(x == y, x.cmp(&y) == Ordering::Equal);
+    assert_eq!(x.cmp(&y), x.to_ordered_bytes().cmp(&y.to_ordered_bytes()));
+  }
+  if let (Ok(x), Ok(y)) = (
+    a.parse::<LexiconKey<Base94>>(),
+    b.parse::<LexiconKey<Base94>>(),
+  ) {
+    assert_eq!(x.cmp(&y), x.to_ordered_bytes().cmp(&y.to_ordered_bytes()));
+  }
+  if let (Ok(x), Ok(y)) = (a.parse::<BalancedKey>(), b.parse::<BalancedKey>()) {
+    assert_eq!(x.cmp(&y), y.cmp(&x).reverse());
+    assert_eq!(x == y, x.cmp(&y) == Ordering::Equal);
+    assert_eq!(x.cmp(&y), x.to_ordered_bytes().cmp(&y.to_ordered_bytes()));
+  }
+  if let (Ok(x), Ok(y)) = (
+    a.parse::<BalancedKey<Base94>>(),
+    b.parse::<BalancedKey<Base94>>(),
+  ) {
+    assert_eq!(x.cmp(&y), x.to_ordered_bytes().cmp(&y.to_ordered_bytes()));
+  }
+});
@@ -0,0 +1,33 @@
+//! parsing any string either fails or round-trips through `Display`,
+//! decoding any bytes either fails or round-trips through `to_ordered_bytes`
+
+#![no_main]
+
+use bisection_key::{BalancedKey, Base94, FractionalIndexKey, LexiconKey};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, &[u8])| {
+  let (s, bytes) = input;
+  if let Ok(k) = s.parse::<LexiconKey>() {
+    assert_eq!(k.to_string(), s);
+  }
+  if let Ok(k) = s.parse::<LexiconKey<Base94>>() {
+    assert_eq!(k.to_string(), s);
+  }
+  if let Ok(k) = s.parse::<BalancedKey>() {
+    assert_eq!(k.to_string(), s);
+  }
+  if let Ok(k) = s.parse::<BalancedKey<Base94>>() {
+    assert_eq!(k.to_string(), s);
+  }
+  if let Ok(k) = s.parse::<FractionalIndexKey>() {
+    assert_eq!(k.to_string(), s);
+  }
+
+  if let Ok(k) = LexiconKey::<Base94>::from_ordered_bytes(bytes) {
+    assert_eq!(k.to_ordered_bytes(), bytes);
+  }
+  if let Ok(k) = BalancedKey::<Base94>::from_ordered_bytes(bytes) {
+    assert_eq!(k.to_ordered_bytes(), bytes);
+  }
+});
@@ -1,29 +1,43 @@
 //! Ordered keys that can be inserted between any two of them infinitely.
 //! like **fractional indexes**, but using variable-length string with custom Ord implementation.
 //!
-//! Using a `[0, 64]` charset, total length: 3 + 10 + 26 + 26 = 65
+//! By default using a `[0, 64]` charset, total length: 3 + 10 + 26 + 26 = 65
 //!
 //! ```text
 //! +-/0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz
 //! ```
 //!
 //! 65 was picked since it's easier to bisect 0~64 at 32, and 0~32 at 16, etc.
+//! Other alphabets are picked with the `Charset` parameter, like `Base36` or `Base94`.
 //!
 //! Notice that `a` equals `aT`, internally `[39]` equals `[39, 32]`.
 //! and `a`(`aT`) is greater than `aS`, this is different from normal order of strings.
 //! So it's called `BalancedKey`, while the lexicographical one is called `LexiconKey`.
 
-use std::cmp::{max, Ordering};
-use std::fmt::Display;
+use alloc::borrow::ToOwned;
+use alloc::string::ToString;
+use alloc::vec::Vec;
 
-const CHARSET: &str = "+-/0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+use core::cmp::{max, min, Ordering};
+use core::fmt::{Display, Write};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use smallvec::smallvec;
+
+use crate::charset::number_digits;
+use crate::digits::Digits;
+use crate::ordered_bytes::{decode_balanced, encode_balanced};
+use crate::spread::{spread_digits, Padding};
+use crate::{Base65, BisectionKeyError, Charset, FractionalKey};
 
 /// create it like:
 /// ```rust
 /// let _  = bisection_key::BalancedKey::new("a");
 /// ```
-#[derive(Debug)]
-pub struct BalancedKey(Vec<u8>);
+#[derive(Debug, Clone)]
+pub struct BalancedKey<C: Charset = Base65>(Digits, PhantomData<C>);
 
 impl Default for BalancedKey {
   fn default() -> Self {
@@ -31,17 +45,17 @@ impl Default for BalancedKey {
   }
 }
 
-impl Eq for BalancedKey {}
+impl<C: Charset> Eq for BalancedKey<C> {}
 
-/// missing length are filled with `32`s, then compare like a vector
-impl PartialEq for BalancedKey {
-  fn eq(&self, other: &BalancedKey) -> bool {
+/// missing length are filled with middle digits, then compare like a vector
+impl<C: Charset> PartialEq for BalancedKey<C> {
+  fn eq(&self, other: &Self) -> bool {
     let xs = &self.0;
     let ys = &other.0;
     let size = max(xs.len(), ys.len());
     for idx in 0..size {
-      let x = xs.get(idx).or(Some(&32));
-      let y = ys.get(idx).or(Some(&32));
+      let x = xs.get(idx).or(Some(&C::MID));
+      let y = ys.get(idx).or(Some(&C::MID));
       if x != y {
         return false;
       }
@@ -50,15 +64,15 @@ impl PartialEq for BalancedKey {
   }
 }
 
-/// missing length are filled with `32`s, then compare like a vector
-impl Ord for BalancedKey {
-  fn cmp(&self, other: &BalancedKey) -> Ordering {
+/// missing length are filled with middle digits, then compare like a vector
+impl<C: Charset> Ord for BalancedKey<C> {
+  fn cmp(&self, other: &Self) -> Ordering {
     let xs = &self.0;
     let ys = &other.0;
     let size = max(xs.len(), ys.len());
     for idx in 0..size {
-      let x = xs.get(idx).or(Some(&32));
-      let y = ys.get(idx).or(Some(&32));
+      let x = xs.get(idx).or(Some(&C::MID));
+      let y = ys.get(idx).or(Some(&C::MID));
       match x.cmp(&y) {
         Ordering::Equal => continue,
         x => return x,
@@ -68,36 +82,88 @@ impl Ord for BalancedKey {
   }
 }
 
-impl PartialOrd for BalancedKey {
-  fn partial_cmp(&self, other: &BalancedKey) -> Option<Ordering> {
+/// trailing middle digits are skipped, since `a` equals `aT`
+impl<C: Charset> Hash for BalancedKey<C> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    let size = self
+      .0
+      .iter()
+      .rposition(|x| *x != C::MID)
+      .map_or(0, |idx| idx + 1);
+    self.0[..size].hash(state);
+  }
+}
+
+impl<C: Charset> PartialOrd for BalancedKey<C> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     Some(self.cmp(other))
   }
 }
 
-impl Display for BalancedKey {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-    let mut buf: String = String::new();
+impl<C: Charset> Display for BalancedKey<C> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
     for i in &self.0 {
-      buf.push(CHARSET.chars().nth(*i as usize).unwrap());
+      f.write_char(C::char_of(*i))?;
     }
-    write!(f, "{}", buf)
+    Ok(())
   }
 }
 
-impl BalancedKey {
-  pub fn new(s: &str) -> Result<Self, String> {
-    let mut buf: Vec<u8> = vec![];
-    for c in s.chars() {
-      match CHARSET.find(c) {
-        Some(i) => buf.push(i as u8),
-        None => return Err(format!("invalid character for bisection key: {:?}", c)),
+impl<C: Charset> FromStr for BalancedKey<C> {
+  type Err = BisectionKeyError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut buf: Digits = smallvec![];
+    for (position, ch) in s.chars().enumerate() {
+      match C::digit_of(ch) {
+        Some(i) => buf.push(i),
+        None => return Err(BisectionKeyError::InvalidChar { ch, position }),
       }
     }
-    Ok(BalancedKey(buf))
+    Ok(Self::of(buf))
+  }
+}
+
+impl<C: Charset> FractionalKey for BalancedKey<C> {
+  fn initial() -> Self {
+    Self::of(smallvec![C::MID])
+  }
+
+  fn before(&self) -> Result<Self, BisectionKeyError> {
+    self.bisect_beginning()
+  }
+
+  fn after(&self) -> Result<Self, BisectionKeyError> {
+    self.bisect_end()
+  }
+
+  fn between(left: Option<&Self>, right: Option<&Self>) -> Result<Self, BisectionKeyError> {
+    Self::between(left, right)
+  }
+
+  fn n_between(
+    left: Option<&Self>,
+    right: Option<&Self>,
+    n: usize,
+  ) -> Result<Vec<Self>, BisectionKeyError> {
+    Self::n_between(left, right, n)
+  }
+}
+
+impl BalancedKey {
+  /// parses a key of the default charset, use `parse` for other charsets
+  pub fn new(s: &str) -> Result<Self, BisectionKeyError> {
+    s.parse()
+  }
+}
+
+impl<C: Charset> BalancedKey<C> {
+  pub(crate) fn of(digits: Digits) -> Self {
+    Self(digits, PhantomData)
   }
 
   pub fn strip_last_mut(&mut self) {
-    while !self.0.is_empty() && self.0[self.0.len() - 1] == 32 {
+    while !self.0.is_empty() && self.0[self.0.len() - 1] == C::MID {
       self.0.pop();
     }
   }
@@ -105,227 +171,312 @@ impl BalancedKey {
   // if last element is `T`, they can safely be removed to shorten the key
   pub fn strip_last(&self) -> Self {
     let mut xs = self.0.to_owned();
-    while !xs.is_empty() && xs[xs.len() - 1] == 32 {
+    while !xs.is_empty() && xs[xs.len() - 1] == C::MID {
       xs.pop();
     }
-    BalancedKey(xs.to_owned())
+    Self::of(xs)
   }
 
-  pub fn bisect(&self, next: &Self) -> Result<Self, String> {
-    let mut mid: Vec<u8> = vec![];
+  /// a key strictly between `self` and `next`, which are taken in either order,
+  /// `a.bisect(&b)` and `b.bisect(&a)` return the same key.
+  pub fn bisect(&self, next: &Self) -> Result<Self, BisectionKeyError> {
+    if self > next {
+      return next.bisect(self);
+    }
 
-    // println!("bisecting {:?} and {:?}", self, next);
+    let mut mid: Digits = smallvec![];
 
-    let mut change: Option<NumberChange> = None;
+    // set at the digit where `next` is larger by 1, digits after that are carried from `self`
+    let mut increased = false;
 
     for i in 0..max(self.0.len(), next.0.len()) {
-      let curr = self.0.get(i).or(Some(&32)).unwrap();
-      let edge = next.0.get(i).or(Some(&32)).unwrap();
-
-      let delta = *edge as i8 - *curr as i8;
-
-      // println!(
-      //   "i:{} curr:{} edge:{} delta:{} change:{:?} mid:{:?}",
-      //   i, *curr, *edge, delta, change, mid
-      // );
-
-      match change {
-        None => {
-          if delta == 0 {
-            mid.push(*curr);
-          } else if delta >= 2 || delta <= -2 {
-            mid.push((edge + curr) >> 1);
-            return Self(mid).checked();
-          } else if delta == 1 {
-            change = Some(NumberChange::Increased);
-            mid.push(curr.to_owned());
-          } else if delta == -1 {
-            change = Some(NumberChange::Decreased);
-            mid.push(curr.to_owned());
-          } else {
-            unreachable!("call cases are exhaustive");
-          }
+      let curr = self.0.get(i).unwrap_or(&C::MID);
+      let edge = next.0.get(i).unwrap_or(&C::MID);
+
+      if !increased {
+        let delta = *edge as i16 - *curr as i16;
+        if delta == 0 {
+          mid.push(*curr);
+        } else if delta >= 2 {
+          mid.push((edge + curr) >> 1);
+          return Self::of(mid).checked();
+        } else if delta == 1 {
+          increased = true;
+          mid.push(curr.to_owned());
+        } else {
+          unreachable!("keys are sorted, first different digit of `next` is larger");
         }
-        Some(NumberChange::Increased) => {
-          let reach = 64 - *curr;
-          // println!("edge:{} reach:{}", edge, reach);
-          match edge.cmp(&reach) {
-            Ordering::Greater => {
-              if *edge == 1 {
-                // which means current is 64, edge is 1
-                mid.push(0);
-                mid = promote_from(mid, i, NumberChange::Increased)?;
-              } else {
-                mid.push((edge - reach) >> 1);
-                mid = promote_from(mid, i, NumberChange::Increased)?;
-                return Self(mid).checked();
-              }
-            }
-            Ordering::Equal => {
-              mid.push(64);
-              if reach == 0 {
-                // need to bisect again
-              } else {
-                return Self(mid).checked();
-              }
-            }
-            Ordering::Less => {
-              if reach == 1 {
-                mid.push(64);
-              } else {
-                mid.push((*curr + edge + 64) >> 1);
-              }
-              return Self(mid).checked();
+      } else {
+        let reach = C::MAX - *curr;
+        match edge.cmp(&reach) {
+          Ordering::Greater => {
+            if *edge == 1 {
+              // which means current is MAX, edge is 1
+              mid.push(0);
+              mid = promote_from::<C>(mid, i)?;
+            } else {
+              mid.push((edge - reach) >> 1);
+              mid = promote_from::<C>(mid, i)?;
+              return Self::of(mid).checked();
             }
           }
-        }
-
-        Some(NumberChange::Decreased) => {
-          let reach = 64 - edge;
-          match reach.cmp(curr) {
-            Ordering::Less => {
-              if *curr == 1 {
-                // which means current is 1, edge is 64
-                mid.push(0);
-              } else {
-                mid.push((*curr - reach) >> 1);
-                return Self(mid).checked();
-              }
+          Ordering::Equal => {
+            mid.push(C::MAX);
+            if reach == 0 {
+              // need to bisect again
+            } else {
+              return Self::of(mid).checked();
             }
-            Ordering::Equal => {
-              mid.push(0);
-              if reach == 0 {
-                // need to bisect again
-              } else {
-                return Self(mid).checked();
-              }
-            }
-            Ordering::Greater => {
-              if reach == 1 {
-                // which means current is 0, edge is 63
-                mid.push(64);
-                mid = promote_from(mid, i, NumberChange::Decreased)?;
-              } else {
-                mid.push((*curr + edge) >> 1);
-              }
-              return Self(mid).checked();
+          }
+          Ordering::Less => {
+            if reach == 1 {
+              mid.push(C::MAX);
+            } else {
+              mid.push((*curr + edge + C::MAX) >> 1);
             }
+            return Self::of(mid).checked();
           }
         }
       }
     }
 
-    match change {
-      None => Err(format!(
-        "not found property way of generating middle key: {} {}",
-        self, next
-      )),
-      Some(NumberChange::Increased) => {
-        mid.push(32 + 2);
-        Self(mid).checked()
+    if increased {
+      mid.push(C::MID + 2);
+      Self::of(mid).checked()
+    } else {
+      let (left, right) = (self.to_string(), next.to_string());
+      if self == next {
+        Err(BisectionKeyError::EqualKeys { left, right })
+      } else {
+        Err(BisectionKeyError::NoKeyBetween { left, right })
       }
-      Some(NumberChange::Decreased) => {
-        mid.push(32 - 2);
-        Self(mid).checked()
+    }
+  }
+
+  /// a key between two optional bounds, `None` for an open side,
+  /// returns an error when `left` is not smaller than `right`.
+  pub fn between(left: Option<&Self>, right: Option<&Self>) -> Result<Self, BisectionKeyError> {
+    match (left, right) {
+      (None, None) => Ok(Self::initial()),
+      (Some(left), None) => left.bisect_end(),
+      (None, Some(right)) => right.bisect_beginning(),
+      (Some(left), Some(right)) => {
+        check_bounds(left, right)?;
+        left.bisect(right)
+      }
+    }
+  }
+
+  /// generates `n` sorted keys between two optional bounds(`None` for an open side),
+  /// keys are spread evenly with the shortest length that leaves enough room.
+  /// returns an error when `left` is not smaller than `right`.
+  pub fn n_between(
+    left: Option<&Self>,
+    right: Option<&Self>,
+    n: usize,
+  ) -> Result<Vec<Self>, BisectionKeyError> {
+    if let (Some(left), Some(right)) = (left, right) {
+      check_bounds(left, right)?;
+    }
+    let spread = spread_digits::<C>(
+      left.map(|k| &k.0[..]),
+      right.map(|k| &k.0[..]),
+      n,
+      Padding::Balanced,
+    );
+    if let Some(xss) = spread {
+      return Ok(xss.into_iter().map(Self::of).collect());
+    }
+
+    // spans too large for numbers, split ranges recursively instead
+    let lo = match left {
+      Some(k) => k.to_owned(),
+      None => Self::lower_bound(right),
+    };
+    let hi = match right {
+      Some(k) => k.to_owned(),
+      None => Self::upper_bound(&lo),
+    };
+    let mut ys: Vec<Self> = Vec::with_capacity(n);
+    Self::spread(&lo, &hi, n, &mut ys)?;
+    Ok(ys)
+  }
+
+  fn spread(lo: &Self, hi: &Self, n: usize, ys: &mut Vec<Self>) -> Result<(), BisectionKeyError> {
+    if n == 0 {
+      return Ok(());
+    }
+    let mid = lo.bisect(hi)?;
+    let half = (n - 1) / 2;
+    Self::spread(lo, &mid, half, ys)?;
+    ys.push(mid.to_owned());
+    Self::spread(&mid, hi, n - 1 - half, ys)
+  }
+
+  /// a key of `0` digits that is smaller than `right`, used as lower bound of an open range
+  fn lower_bound(right: Option<&Self>) -> Self {
+    let size = right.map_or(0, |k| k.0.iter().take_while(|x| **x == 0).count());
+    Self::of(smallvec![0; size + 1])
+  }
+
+  /// a key of largest digits that is larger than `left`, used as upper bound of an open range
+  fn upper_bound(left: &Self) -> Self {
+    let size = left.0.iter().take_while(|x| **x == C::MAX).count();
+    Self::of(smallvec![C::MAX; size + 1])
+  }
+
+  /// like `bisect`, but appends digits of `client_id` to the middle key,
+  /// so that clients inserting between the same keys concurrently get different keys.
+  /// Result still lies strictly between both keys.
+  pub fn bisect_unique(&self, next: &Self, client_id: u64) -> Result<Self, BisectionKeyError> {
+    let (lo, hi) = (min(self, next), max(self, next));
+    let mut mid = self.bisect(next)?;
+    // appended digits keep a key between bounds only when it differs from both bounds
+    // within its own digits, otherwise the order is decided by the appended digits
+    loop {
+      if !mid.differs_within(hi) {
+        mid = mid.bisect(hi)?;
+      } else if !mid.differs_within(lo) {
+        mid = lo.bisect(&mid)?;
+      } else {
+        break;
       }
     }
+    let mut ys = mid.0;
+    ys.extend(number_digits::<C>(client_id));
+    // not ending with a middle digit, so that keys of different ids are never equal
+    ys.push(C::MID + 1);
+    Self::of(ys).checked()
   }
 
-  pub fn bisect_end(&self) -> Result<Self, String> {
-    let mut ys: Vec<u8> = vec![];
-    for item in &self.0 {
-      if *item > 64 {
-        return Err(format!("invalid key: {}", self));
-      } else if *item == 64 {
-        ys.push(64);
-      } else if *item == 63 {
-        ys.push(64);
-        return Self(ys).checked();
+  fn differs_within(&self, other: &Self) -> bool {
+    self
+      .0
+      .iter()
+      .enumerate()
+      .any(|(idx, x)| other.0.get(idx).unwrap_or(&C::MID) != x)
+  }
+
+  pub fn bisect_end(&self) -> Result<Self, BisectionKeyError> {
+    let mut ys: Digits = smallvec![];
+    for (position, item) in self.0.iter().enumerate() {
+      if *item > C::MAX {
+        return Err(BisectionKeyError::DigitOutOfRange {
+          digit: *item,
+          position,
+        });
+      } else if *item == C::MAX {
+        ys.push(C::MAX);
+      } else if *item == C::MAX - 1 {
+        ys.push(C::MAX);
+        return Self::of(ys).checked();
       } else {
-        // max 62
+        // at most MAX - 2
         ys.push(*item + 2);
-        return Self(ys).checked();
+        return Self::of(ys).checked();
       }
     }
-    ys.push(32 + 2);
-    Self(ys).checked()
+    ys.push(C::MID + 2);
+    Self::of(ys).checked()
   }
 
-  pub fn bisect_beginning(&self) -> Result<Self, String> {
-    let mut ys: Vec<u8> = vec![];
-    for item in &self.0 {
-      if *item > 64 {
-        return Err(format!("invalid key: {}", self));
+  pub fn bisect_beginning(&self) -> Result<Self, BisectionKeyError> {
+    let mut ys: Digits = smallvec![];
+    for (position, item) in self.0.iter().enumerate() {
+      if *item > C::MAX {
+        return Err(BisectionKeyError::DigitOutOfRange {
+          digit: *item,
+          position,
+        });
       } else if *item == 0 {
         ys.push(0);
       } else if *item == 1 {
         ys.push(0);
-        return Self(ys).checked();
+        return Self::of(ys).checked();
       } else {
         // min 2
         ys.push(*item - 2);
-        return Self(ys).checked();
+        return Self::of(ys).checked();
       }
     }
-    ys.push(32 - 2);
-    Self(ys).checked()
+    ys.push(C::MID - 2);
+    Self::of(ys).checked()
+  }
+
+  /// bytes whose `memcmp` order equals the order of keys, see `ORDERED_BYTES_VERSION` for the format
+  pub fn to_ordered_bytes(&self) -> Vec<u8> {
+    encode_balanced::<C>(&self.0)
+  }
+
+  pub fn from_ordered_bytes(bytes: &[u8]) -> Result<Self, BisectionKeyError> {
+    Ok(Self::of(decode_balanced::<C>(bytes)?))
   }
 
-  pub fn promote_from(&self, idx: usize, change: NumberChange) -> Result<Self, String> {
-    Ok(Self(promote_from(self.0.to_owned(), idx, change)?))
+  /// adds 1 to the digit before `idx`, carrying into digits before it
+  pub fn promote_from(&self, idx: usize) -> Result<Self, BisectionKeyError> {
+    Ok(Self::of(promote_from::<C>(self.0.clone(), idx)?))
   }
 
-  pub fn checked(self) -> Result<Self, String> {
-    // println!("checking {:?}", self);
-    // check
-    for i in 0..self.0.len() {
-      if self.0[i] > 64 {
-        return Err(format!(
-          "invalid character for bisection key: {:?}",
-          self.0[i]
-        ));
+  pub fn checked(self) -> Result<Self, BisectionKeyError> {
+    for (position, digit) in self.0.iter().enumerate() {
+      if *digit > C::MAX {
+        return Err(BisectionKeyError::DigitOutOfRange {
+          digit: *digit,
+          position,
+        });
       }
     }
     Ok(self)
   }
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
-pub enum NumberChange {
-  Increased,
-  Decreased,
+fn check_bounds<C: Charset>(
+  left: &BalancedKey<C>,
+  right: &BalancedKey<C>,
+) -> Result<(), BisectionKeyError> {
+  match left.cmp(right) {
+    Ordering::Less => Ok(()),
+    Ordering::Equal => Err(BisectionKeyError::EqualKeys {
+      left: left.to_string(),
+      right: right.to_string(),
+    }),
+    Ordering::Greater => Err(BisectionKeyError::ReversedBounds {
+      left: left.to_string(),
+      right: right.to_string(),
+    }),
+  }
 }
 
-fn promote_from(base: Vec<u8>, origin_idx: usize, change: NumberChange) -> Result<Vec<u8>, String> {
+fn promote_from<C: Charset>(
+  mut xs: Digits,
+  origin_idx: usize,
+) -> Result<Digits, BisectionKeyError> {
   if origin_idx == 0 {
-    return Err(format!("cannot promote from 0 of: {:?}", base));
+    return Err(BisectionKeyError::NoSpaceToPromote {
+      digits: xs.to_vec(),
+    });
   }
   let idx = origin_idx - 1;
-  if idx >= base.len() {
-    Err(format!("index out of range: {:?} {}", base, idx))
-  } else {
-    let mut xs = base.to_owned();
-    let mut pos = idx;
-    loop {
-      if change == NumberChange::Decreased {
-        if xs[pos] == 0 {
-          xs[pos] = 64;
-        } else {
-          xs[pos] -= 1;
-          break Ok(xs);
-        }
-      } else if xs[pos] == 64 {
-        xs[pos] = 0;
-      } else {
-        xs[pos] += 1;
-        break Ok(xs);
-      }
-
-      if pos > 0 {
-        pos -= 1;
-      } else {
-        return Err(format!("not found position to promote: {:?}", base));
-      }
+  if idx >= xs.len() {
+    return Err(BisectionKeyError::IndexOutOfRange {
+      index: idx,
+      len: xs.len(),
+    });
+  }
+  // carrying past the first digit, checked before touching digits
+  if xs[..=idx].iter().all(|x| *x == C::MAX) {
+    return Err(BisectionKeyError::NoSpaceToPromote {
+      digits: xs.to_vec(),
+    });
+  }
+  let mut pos = idx;
+  loop {
+    if xs[pos] == C::MAX {
+      xs[pos] = 0;
+    } else {
+      xs[pos] += 1;
+      return Ok(xs);
     }
+    pos -= 1;
   }
 }
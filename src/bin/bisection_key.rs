@@ -0,0 +1,199 @@
+//! Generating and inspecting keys from the command line, keys are read and written line by line.
+//!
+//! ```text
+//! bisection-key between a b
+//! bisection-key --balanced spread "" "" --count 10
+//! bisection-key rebalance < keys.txt
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use bisection_key::{rebalance, BalancedKey, BisectionKeyError, FractionalKey, LexiconKey};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[command(
+  name = "bisection-key",
+  version,
+  about = "generate and inspect bisection keys"
+)]
+struct Cli {
+  /// keys where missing digits are middle digits, as `BalancedKey`,
+  /// otherwise keys of lexicographical order, as `LexiconKey`
+  #[arg(long, global = true)]
+  balanced: bool,
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+  /// a key between two keys, empty for an open side
+  Between {
+    #[arg(allow_hyphen_values = true)]
+    left: String,
+    #[arg(allow_hyphen_values = true)]
+    right: String,
+  },
+  /// a key before a key
+  Before {
+    #[arg(allow_hyphen_values = true)]
+    key: String,
+  },
+  /// a key after a key
+  After {
+    #[arg(allow_hyphen_values = true)]
+    key: String,
+  },
+  /// keys spread evenly between two keys, empty for an open side
+  Spread {
+    #[arg(allow_hyphen_values = true)]
+    left: String,
+    #[arg(allow_hyphen_values = true)]
+    right: String,
+    #[arg(long, short = 'n', default_value_t = 10)]
+    count: usize,
+  },
+  /// checks keys, from arguments or from stdin
+  Validate {
+    #[arg(allow_hyphen_values = true)]
+    keys: Vec<String>,
+  },
+  /// prints `<`, `=` or `>`
+  Compare {
+    #[arg(allow_hyphen_values = true)]
+    left: String,
+    #[arg(allow_hyphen_values = true)]
+    right: String,
+  },
+  /// reissues short keys for keys from stdin, by their positions
+  Rebalance,
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+  let result = if cli.balanced {
+    run::<BalancedKey>(cli.command)
+  } else {
+    run::<LexiconKey>(cli.command)
+  };
+  match result {
+    Ok(code) => code,
+    // stdout closed early, like piping into `head`
+    Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("error: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// failures of keys, or of reading stdin and writing stdout
+#[derive(Debug)]
+enum CliError {
+  Key(BisectionKeyError),
+  Io(io::Error),
+}
+
+impl Display for CliError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Key(e) => write!(f, "{}", e),
+      Self::Io(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl From<BisectionKeyError> for CliError {
+  fn from(e: BisectionKeyError) -> Self {
+    Self::Key(e)
+  }
+}
+
+impl From<io::Error> for CliError {
+  fn from(e: io::Error) -> Self {
+    Self::Io(e)
+  }
+}
+
+fn run<K: FractionalKey>(command: Command) -> Result<ExitCode, CliError> {
+  let mut out = io::stdout().lock();
+  match command {
+    Command::Between { left, right } => {
+      let (left, right) = (open_key::<K>(&left)?, open_key::<K>(&right)?);
+      writeln!(out, "{}", K::between(left.as_ref(), right.as_ref())?)?;
+    }
+    Command::Before { key } => writeln!(out, "{}", key.parse::<K>()?.before()?)?,
+    Command::After { key } => writeln!(out, "{}", key.parse::<K>()?.after()?)?,
+    Command::Spread { left, right, count } => {
+      let (left, right) = (open_key::<K>(&left)?, open_key::<K>(&right)?);
+      write_keys(
+        &mut out,
+        &K::n_between(left.as_ref(), right.as_ref(), count)?,
+      )?;
+    }
+    Command::Validate { keys } => {
+      let keys = if keys.is_empty() { read_lines()? } else { keys };
+      let mut valid = true;
+      for key in keys {
+        if let Err(e) = key.parse::<K>() {
+          writeln!(out, "{}\t{}", key, e)?;
+          valid = false;
+        }
+      }
+      if !valid {
+        return Ok(ExitCode::FAILURE);
+      }
+    }
+    Command::Compare { left, right } => {
+      let ordering = left.parse::<K>()?.cmp(&right.parse::<K>()?);
+      let sign = match ordering {
+        Ordering::Less => "<",
+        Ordering::Equal => "=",
+        Ordering::Greater => ">",
+      };
+      writeln!(out, "{}", sign)?;
+    }
+    Command::Rebalance => {
+      let keys: Vec<K> = read_lines()?
+        .iter()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?;
+      write_keys(&mut out, &rebalance(&keys)?)?;
+    }
+  }
+  Ok(ExitCode::SUCCESS)
+}
+
+/// empty for an open side, `-` can't be used since it's a digit of the default charset
+fn open_key<K: FractionalKey>(s: &str) -> Result<Option<K>, BisectionKeyError> {
+  if s.is_empty() {
+    Ok(None)
+  } else {
+    s.parse().map(Some)
+  }
+}
+
+/// non-empty lines from stdin, trimmed. Fails on the first line that can't be read,
+/// instead of working on part of the keys
+fn read_lines() -> io::Result<Vec<String>> {
+  let mut ys: Vec<String> = vec![];
+  for line in io::stdin().lock().lines() {
+    let line = line?;
+    let line = line.trim();
+    if !line.is_empty() {
+      ys.push(line.to_owned());
+    }
+  }
+  Ok(ys)
+}
+
+fn write_keys<K: FractionalKey>(out: &mut impl Write, keys: &[K]) -> io::Result<()> {
+  for k in keys {
+    writeln!(out, "{}", k)?;
+  }
+  Ok(())
+}
@@ -0,0 +1,150 @@
+//! Alphabets for keys. Digits are positions of characters in the charset,
+//! the largest digit and the middle digit are derived from its size.
+//!
+//! ```text
+//! Base36  0123456789abcdefghijklmnopqrstuvwxyz
+//! Base62  0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz
+//! Base65  +-/0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz
+//! Base94  all printable ASCII characters from `!` to `~`
+//! ```
+
+use core::fmt::Debug;
+use core::hash::Hash;
+
+use smallvec::smallvec;
+
+use crate::digits::Digits;
+
+/// A charset of ASCII characters, in the order of digits.
+///
+/// ```rust
+/// use bisection_key::{Base36, LexiconKey};
+///
+/// let k: LexiconKey<Base36> = "h".parse().unwrap();
+/// assert_eq!(k.bisect_end().unwrap().to_string(), "j");
+/// ```
+///
+/// Charsets are checked at compile time where they are used, this one repeats `0`:
+///
+/// ```compile_fail
+/// use bisection_key::{Charset, LexiconKey};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// struct Repeated;
+///
+/// impl Charset for Repeated {
+///   const CHARS: &'static str = "01234567890";
+/// }
+///
+/// let k: LexiconKey<Repeated> = "1".parse().unwrap();
+/// ```
+pub trait Charset:
+  Debug + Clone + Copy + PartialEq + Eq + Hash + Default + Send + Sync + 'static
+{
+  /// characters of digits from `0`, at least 8 and at most 128 ASCII characters
+  const CHARS: &'static str;
+
+  /// number of digits, fails to compile when `CHARS` is not a valid charset
+  const SIZE: u8 = checked_size(Self::CHARS.as_bytes());
+
+  /// largest digit
+  const MAX: u8 = Self::SIZE - 1;
+
+  /// digit in the middle, picked for the first key
+  const MID: u8 = Self::MAX / 2;
+
+  /// whether string order of keys matches the order of digits,
+  /// which is required for storing `LexiconKey` in plain text columns
+  const SORT_COMPATIBLE: bool = is_ascending(Self::CHARS.as_bytes());
+
+  fn digit_of(ch: char) -> Option<u8> {
+    if ch.is_ascii() {
+      (0..Self::SIZE).find(|d| Self::char_of(*d) == ch)
+    } else {
+      None
+    }
+  }
+
+  fn char_of(digit: u8) -> char {
+    Self::CHARS.as_bytes()[digit as usize] as char
+  }
+}
+
+/// digits of a number in the base of charset size, most significant digit first
+pub(crate) fn number_digits<C: Charset>(mut x: u64) -> Digits {
+  let mut ys: Digits = smallvec![(x % C::SIZE as u64) as u8];
+  x /= C::SIZE as u64;
+  while x > 0 {
+    ys.push((x % C::SIZE as u64) as u8);
+    x /= C::SIZE as u64;
+  }
+  ys.reverse();
+  ys
+}
+
+/// size of a charset, panics at compile time for charsets out of 8 to 128 characters,
+/// with non-ASCII characters or with repeated characters
+const fn checked_size(xs: &[u8]) -> u8 {
+  assert!(
+    xs.len() >= 8 && xs.len() <= 128,
+    "charset needs 8 to 128 characters"
+  );
+  let mut idx = 0;
+  while idx < xs.len() {
+    assert!(xs[idx].is_ascii(), "charset needs ASCII characters");
+    let mut prev = 0;
+    while prev < idx {
+      assert!(xs[prev] != xs[idx], "charset has repeated characters");
+      prev += 1;
+    }
+    idx += 1;
+  }
+  xs.len() as u8
+}
+
+const fn is_ascending(xs: &[u8]) -> bool {
+  let mut idx = 1;
+  while idx < xs.len() {
+    if xs[idx - 1] >= xs[idx] {
+      return false;
+    }
+    idx += 1;
+  }
+  true
+}
+
+/// digits and lowercase letters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base36;
+
+impl Charset for Base36 {
+  const CHARS: &'static str = "0123456789abcdefghijklmnopqrstuvwxyz";
+}
+
+/// digits, uppercase and lowercase letters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base62;
+
+impl Charset for Base62 {
+  const CHARS: &'static str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+}
+
+/// the default charset, 65 was picked since it's easier to bisect 0~64 at 32, and 0~32 at 16, etc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base65;
+
+impl Charset for Base65 {
+  const CHARS: &'static str = "+-/0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+}
+
+/// printable ASCII characters, for shorter keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base94;
+
+impl Charset for Base94 {
+  const CHARS: &'static str = concat!(
+    "!\"#$%&'()*+,-./0123456789:;<=>?@",
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`",
+    "abcdefghijklmnopqrstuvwxyz{|}~"
+  );
+}
@@ -0,0 +1,160 @@
+//! Migrating keys between `BalancedKey` and `LexiconKey`.
+//!
+//! Orders of both key types are different, `a` is smaller than `aT` in `LexiconKey`
+//! but equal to it in `BalancedKey`, so keys are mapped into longer keys that keep the order.
+//! Digits are kept, only a few digits are escaped and a terminator is appended, by default:
+//!
+//! ```text
+//! BalancedKey -> LexiconKey     aS -> aSTT    aT+ -> aT++TT    a -> aTT    aTz -> aTzzTT
+//! LexiconKey  -> BalancedKey     a -> a+      a+ -> a+z+        aS -> aS+
+//! ```
+//!
+//! - `BalancedKey` to `LexiconKey`, trailing middle digits `M` are stripped first.
+//!   Other middle digits are written as `M 0` when the next digit that is not `M` is smaller,
+//!   otherwise as `M MAX`, and the key ends with `M M`, the same idea as ordered bytes.
+//! - `LexiconKey` to `BalancedKey`, digits `0` are written as `0 MAX` and the key ends with `0`,
+//!   which is smaller than anything continuing.
+//!
+//! Conversions are lossless, `restore_balanced` and `restore_lexicon` get original keys back
+//! from converted keys. To get short keys instead, `rebalance` the list after converting.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use smallvec::smallvec;
+
+use crate::digits::Digits;
+use crate::{BalancedKey, BisectionKeyError, Charset, LexiconKey};
+
+impl<C: Charset> From<&BalancedKey<C>> for LexiconKey<C> {
+  /// a `LexiconKey` in the same order among converted keys
+  fn from(key: &BalancedKey<C>) -> Self {
+    let mut digits: Digits = smallvec![];
+    // bytes are `d + 1` below middle, `d + 3` above, and middle digits mark directions
+    for b in &key.to_ordered_bytes()[1..] {
+      let b = *b;
+      if b <= C::MID {
+        digits.push(b - 1);
+      } else if b == C::MID + 1 {
+        digits.extend([C::MID, 0]);
+      } else if b == C::MID + 2 {
+        digits.extend([C::MID, C::MID]);
+      } else if b == C::MID + 3 {
+        digits.extend([C::MID, C::MAX]);
+      } else {
+        digits.push(b - 3);
+      }
+    }
+    LexiconKey::of(digits)
+  }
+}
+
+impl<C: Charset> From<&LexiconKey<C>> for BalancedKey<C> {
+  /// a `BalancedKey` in the same order among converted keys
+  fn from(key: &LexiconKey<C>) -> Self {
+    let mut digits: Digits = smallvec![];
+    for b in &key.to_ordered_bytes()[1..] {
+      match b - 1 {
+        0 => digits.extend([0, C::MAX]),
+        d => digits.push(d),
+      }
+    }
+    digits.push(0);
+    BalancedKey::of(digits)
+  }
+}
+
+/// converts a list of `BalancedKey` into `LexiconKey` of the same charset key by key,
+/// for storing keys in text columns sorted by `ORDER BY`. Order of the list is kept.
+///
+/// ```rust
+/// use bisection_key::{balanced_to_lexicon, BalancedKey};
+///
+/// let keys: Vec<BalancedKey> = ["aS", "a", "aU"].iter().map(|s| s.parse().unwrap()).collect();
+/// let ys = balanced_to_lexicon(&keys);
+/// assert_eq!(ys[1].to_string(), "aTT");
+/// assert!(ys[0] < ys[1] && ys[1] < ys[2]);
+/// ```
+pub fn balanced_to_lexicon<C: Charset>(keys: &[BalancedKey<C>]) -> Vec<LexiconKey<C>> {
+  keys.iter().map(LexiconKey::from).collect()
+}
+
+/// converts a list of `LexiconKey` into `BalancedKey` of the same charset key by key
+pub fn lexicon_to_balanced<C: Charset>(keys: &[LexiconKey<C>]) -> Vec<BalancedKey<C>> {
+  keys.iter().map(BalancedKey::from).collect()
+}
+
+/// the `BalancedKey` a `LexiconKey` was converted from, the inverse of `LexiconKey::from`.
+/// Returns `InvalidKey` for keys that are not converted from any `BalancedKey`.
+///
+/// ```rust
+/// use bisection_key::{restore_balanced, BalancedKey, LexiconKey};
+///
+/// let k = BalancedKey::new("aT+").unwrap();
+/// assert_eq!(restore_balanced(&LexiconKey::from(&k)), Ok(k));
+/// ```
+pub fn restore_balanced<C: Charset>(
+  key: &LexiconKey<C>,
+) -> Result<BalancedKey<C>, BisectionKeyError> {
+  let invalid = || BisectionKeyError::InvalidKey {
+    key: key.to_string(),
+  };
+  let mut digits: Digits = smallvec![];
+  let bytes = key.to_ordered_bytes();
+  let mut xs = bytes[1..].iter().map(|b| b - 1);
+  loop {
+    match xs.next().ok_or_else(invalid)? {
+      d if d != C::MID => digits.push(d),
+      _ => match xs.next().ok_or_else(invalid)? {
+        d if d == C::MID => break,
+        _ => digits.push(C::MID),
+      },
+    }
+  }
+  let restored = BalancedKey::of(digits);
+  // directions of middle digits and the end are checked by converting again
+  if xs.next().is_none() && &LexiconKey::from(&restored) == key {
+    Ok(restored)
+  } else {
+    Err(invalid())
+  }
+}
+
+/// the `LexiconKey` a `BalancedKey` was converted from, the inverse of `BalancedKey::from`.
+/// Returns `InvalidKey` for keys that are not converted from any `LexiconKey`.
+pub fn restore_lexicon<C: Charset>(
+  key: &BalancedKey<C>,
+) -> Result<LexiconKey<C>, BisectionKeyError> {
+  let invalid = || BisectionKeyError::InvalidKey {
+    key: key.to_string(),
+  };
+  // canonical digits from ordered bytes, trailing middle digits are stripped there
+  let bytes = key.to_ordered_bytes();
+  let mut xs = bytes[1..].iter().filter_map(|b| {
+    if *b <= C::MID {
+      Some(b - 1)
+    } else if *b == C::MID + 2 {
+      None
+    } else if *b <= C::MID + 3 {
+      Some(C::MID)
+    } else {
+      Some(b - 3)
+    }
+  });
+  let mut digits: Digits = smallvec![];
+  loop {
+    match xs.next().ok_or_else(invalid)? {
+      0 => match xs.next() {
+        None => break,
+        Some(_) => digits.push(0),
+      },
+      d => digits.push(d),
+    }
+  }
+  let restored = LexiconKey::of(digits);
+  if &BalancedKey::from(&restored) == key {
+    Ok(restored)
+  } else {
+    Err(invalid())
+  }
+}
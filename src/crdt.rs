@@ -0,0 +1,340 @@
+//! A replicated list for collaborative editing, every replica applies operations of others
+//! and ends up with the same order, whatever order operations arrive in.
+//!
+//! Items are placed at `Position`s, a `LexiconKey` followed by the replica id and counter
+//! that created the position, so positions are unique even when concurrent `bisect` calls
+//! return equal keys. Keys generated locally carry digits of the replica id and the counter
+//! as well(see `LexiconKey::bisect_unique`), which keeps room between items of different replicas.
+//!
+//! - a move gives an item a new position, the latest position wins,
+//!   compared by counter(a Lamport clock) then replica id.
+//! - a delete leaves a tombstone of the item, later moves or late insertions don't bring it back.
+//!
+//! Applying an operation twice does nothing, so operations can be delivered at least once.
+//!
+//! ```rust
+//! use bisection_key::crdt::ReplicatedList;
+//!
+//! let mut alice = ReplicatedList::new(1);
+//! let mut bob = ReplicatedList::new(2);
+//! let a = alice.insert(0, 'a').unwrap();
+//! let b = bob.insert(0, 'b').unwrap();
+//! alice.apply(b);
+//! bob.apply(a);
+//! assert_eq!(alice.values().collect::<String>(), bob.values().collect::<String>());
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use core::cmp::max;
+use core::ops::Bound::{Excluded, Unbounded};
+
+use crate::{BisectionKeyError, FractionalKey, LexiconKey};
+
+/// id of a replica, expected to be unique among replicas of a list
+pub type ReplicaId = u64;
+
+/// identity of an item, the replica and counter of the insertion that created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId {
+  pub replica: ReplicaId,
+  pub counter: u64,
+}
+
+/// place of an item in the list, ordered by key first, then replica id and counter
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+  pub key: LexiconKey,
+  pub replica: ReplicaId,
+  pub counter: u64,
+}
+
+impl Position {
+  /// timestamp for picking the latest position of an item
+  fn stamp(&self) -> (u64, ReplicaId) {
+    (self.counter, self.replica)
+  }
+}
+
+/// operation made by a replica, to be applied by every other replica
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op<V> {
+  Insert {
+    id: ItemId,
+    position: Position,
+    value: V,
+  },
+  Move {
+    id: ItemId,
+    position: Position,
+  },
+  Delete {
+    id: ItemId,
+  },
+}
+
+/// a list replicated across replicas, local edits return operations for sending to others
+#[derive(Debug, Clone)]
+pub struct ReplicatedList<V> {
+  replica: ReplicaId,
+  clock: u64,
+  /// latest known position of every item not deleted, including moves of items not inserted yet
+  positions: BTreeMap<ItemId, Position>,
+  values: BTreeMap<ItemId, V>,
+  tombstones: BTreeSet<ItemId>,
+  /// visible items in order
+  order: BTreeMap<Position, ItemId>,
+}
+
+impl<V> ReplicatedList<V> {
+  pub fn new(replica: ReplicaId) -> Self {
+    Self {
+      replica,
+      clock: 0,
+      positions: BTreeMap::new(),
+      values: BTreeMap::new(),
+      tombstones: BTreeSet::new(),
+      order: BTreeMap::new(),
+    }
+  }
+
+  pub fn replica(&self) -> ReplicaId {
+    self.replica
+  }
+
+  pub fn len(&self) -> usize {
+    self.order.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.order.is_empty()
+  }
+
+  /// value at `index`, walks the list from the front
+  pub fn get(&self, index: usize) -> Option<&V> {
+    self.id_at(index).and_then(|id| self.values.get(&id))
+  }
+
+  /// id of the item at `index`, walks the list from the front
+  pub fn id_at(&self, index: usize) -> Option<ItemId> {
+    self.order.values().nth(index).copied()
+  }
+
+  pub fn position_of(&self, id: &ItemId) -> Option<&Position> {
+    self
+      .positions
+      .get(id)
+      .filter(|_| self.values.contains_key(id))
+  }
+
+  pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + '_ {
+    self.order.values().map(|id| &self.values[id])
+  }
+
+  /// ids and values in order
+  pub fn iter(&self) -> impl DoubleEndedIterator<Item = (ItemId, &V)> + '_ {
+    self.order.values().map(|id| (*id, &self.values[id]))
+  }
+
+  /// deletes the item at `index`, `None` when there's no item
+  pub fn delete(&mut self, index: usize) -> Option<Op<V>> {
+    let id = self.id_at(index)?;
+    self.apply(Op::Delete { id });
+    Some(Op::Delete { id })
+  }
+
+  /// moves the item at `from` so that it ends up at `to`
+  pub fn move_item(&mut self, from: usize, to: usize) -> Result<Op<V>, BisectionKeyError> {
+    let len = self.order.len();
+    for index in [from, to] {
+      if index >= len {
+        return Err(BisectionKeyError::IndexOutOfRange { index, len });
+      }
+    }
+    let id = self.id_at(from).expect("checked index");
+    let position = self.position_at(to, Some(id))?;
+    self.apply(Op::Move {
+      id,
+      position: position.to_owned(),
+    });
+    Ok(Op::Move { id, position })
+  }
+
+  /// applies an operation from any replica, including operations applied before
+  pub fn apply(&mut self, op: Op<V>) {
+    match op {
+      Op::Insert {
+        id,
+        position,
+        value,
+      } => {
+        self.observe(&position);
+        if self.tombstones.contains(&id) {
+          return;
+        }
+        self.values.entry(id).or_insert(value);
+        self.place(id, position);
+      }
+      Op::Move { id, position } => {
+        self.observe(&position);
+        if self.tombstones.contains(&id) {
+          return;
+        }
+        self.place(id, position);
+      }
+      Op::Delete { id } => {
+        self.tombstones.insert(id);
+        self.values.remove(&id);
+        if let Some(position) = self.positions.remove(&id) {
+          self.order.remove(&position);
+        }
+      }
+    }
+  }
+
+  /// keeps the latest position of an item, and the order of visible items
+  fn place(&mut self, id: ItemId, position: Position) {
+    let latest = match self.positions.remove(&id) {
+      Some(p) if p.stamp() > position.stamp() => p,
+      Some(p) => {
+        self.order.remove(&p);
+        position
+      }
+      None => position,
+    };
+    if self.values.contains_key(&id) {
+      self.order.insert(latest.to_owned(), id);
+    }
+    self.positions.insert(id, latest);
+  }
+
+  fn observe(&mut self, position: &Position) {
+    self.clock = max(self.clock, position.counter);
+  }
+
+  /// a new position at `index` among items other than `skipped`, ticks the clock.
+  /// Between two items of equal keys, from concurrent insertions, the replica id and counter
+  /// of the new position decide whether it fits between them. When it doesn't,
+  /// the position goes right after all items of the same key, later than `index`.
+  fn position_at(
+    &mut self,
+    index: usize,
+    skipped: Option<ItemId>,
+  ) -> Result<Position, BisectionKeyError> {
+    let mut others = self
+      .order
+      .iter()
+      .filter(|(_, id)| Some(**id) != skipped)
+      .map(|(p, _)| p);
+    let (left, right) = if index == 0 {
+      (None, others.next())
+    } else {
+      let mut others = others.skip(index - 1);
+      (others.next(), others.next())
+    };
+    let counter = self.clock + 1;
+    let right = match (left, right) {
+      (Some(l), Some(r)) if l.key == r.key => {
+        let stamp = (self.replica, counter);
+        if (l.replica, l.counter) < stamp && stamp < (r.replica, r.counter) {
+          // positions of equal keys are ordered by replica id and counter
+          self.clock = counter;
+          return Ok(Position {
+            key: l.key.to_owned(),
+            replica: self.replica,
+            counter,
+          });
+        }
+        // no key goes between equal keys
+        self
+          .order
+          .range((Excluded(l), Unbounded))
+          .filter(|(_, id)| Some(**id) != skipped)
+          .map(|(p, _)| p)
+          .find(|p| p.key > l.key)
+      }
+      _ => right,
+    };
+    let key = unique_key(
+      left.map(|p| &p.key),
+      right.map(|p| &p.key),
+      self.replica,
+      counter,
+    )?;
+    self.clock = counter;
+    Ok(Position {
+      key,
+      replica: self.replica,
+      counter,
+    })
+  }
+}
+
+impl<V: Clone> ReplicatedList<V> {
+  /// inserts a value so that it ends up at `index`, `len()` for the end
+  pub fn insert(&mut self, index: usize, value: V) -> Result<Op<V>, BisectionKeyError> {
+    let len = self.order.len();
+    if index > len {
+      return Err(BisectionKeyError::IndexOutOfRange { index, len });
+    }
+    let position = self.position_at(index, None)?;
+    let id = ItemId {
+      replica: self.replica,
+      counter: position.counter,
+    };
+    let op = Op::Insert {
+      id,
+      position,
+      value,
+    };
+    self.apply(op.clone());
+    Ok(op)
+  }
+
+  /// merges the whole state of another replica, same as applying all of its operations
+  pub fn merge(&mut self, other: &Self) {
+    self.clock = max(self.clock, other.clock);
+    for id in &other.tombstones {
+      self.apply(Op::Delete { id: *id });
+    }
+    for (id, position) in &other.positions {
+      match other.values.get(id) {
+        Some(value) => self.apply(Op::Insert {
+          id: *id,
+          position: position.to_owned(),
+          value: value.to_owned(),
+        }),
+        None => self.apply(Op::Move {
+          id: *id,
+          position: position.to_owned(),
+        }),
+      }
+    }
+  }
+}
+
+/// a key between two keys with digits of the replica id and the counter appended,
+/// so replicas inserting at the same place concurrently get different keys,
+/// and a replica never reuses a key of an item that was moved away
+fn unique_key(
+  left: Option<&LexiconKey>,
+  right: Option<&LexiconKey>,
+  replica: ReplicaId,
+  counter: u64,
+) -> Result<LexiconKey, BisectionKeyError> {
+  let (lo, hi) = match (left, right) {
+    (Some(l), Some(r)) => (l.to_owned(), r.to_owned()),
+    (Some(l), None) => (l.to_owned(), l.after()?),
+    (None, Some(r)) => (r.before()?, r.to_owned()),
+    (None, None) => {
+      let k = LexiconKey::initial();
+      let after = k.after()?;
+      (k, after)
+    }
+  };
+  // `hi` never starts with the key from `bisect_unique`, so appended digits keep it below `hi`
+  let mut key = lo.bisect_unique(&hi, replica)?;
+  key.push_number(counter);
+  Ok(key)
+}
@@ -0,0 +1,9 @@
+//! Storage of digits. Keys are almost always short, so digits are kept inline,
+//! and only keys longer than `INLINE_DIGITS` spill to the heap.
+
+use smallvec::SmallVec;
+
+/// inline capacity, keeps `Digits` as small as a `Vec<u8>` on 64-bit targets
+pub(crate) const INLINE_DIGITS: usize = 16;
+
+pub(crate) type Digits = SmallVec<[u8; INLINE_DIGITS]>;
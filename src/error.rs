@@ -0,0 +1,86 @@
+//! Errors shared by `LexiconKey` and `BalancedKey`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt::{self, Display};
+
+/// failures from parsing keys or generating keys between them
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BisectionKeyError {
+  /// character not found in the charset, `position` counts in chars
+  InvalidChar { ch: char, position: usize },
+  /// digit larger than the largest digit of the charset
+  DigitOutOfRange { digit: u8, position: usize },
+  /// bisecting a key with itself
+  EqualKeys { left: String, right: String },
+  /// left bound is larger than right bound
+  ReversedBounds { left: String, right: String },
+  /// keys are different, but no key exists between them, like `a` and `a+`
+  NoKeyBetween { left: String, right: String },
+  /// carrying went past the first digit, or started from index `0`
+  NoSpaceToPromote { digits: Vec<u8> },
+  /// promoting from a digit that does not exist
+  IndexOutOfRange { index: usize, len: usize },
+  /// ordered bytes not produced by `to_ordered_bytes`, `position` counts in bytes
+  InvalidBytes { position: usize },
+  /// ordered bytes written by an unknown version of format
+  UnsupportedVersion { version: u8 },
+  /// key is made of `0` digits(`+` by default), nothing goes before it
+  TrailingMinimum { key: String },
+  /// key not in the expected format, like a wrong length of integer part of `FractionalIndexKey`,
+  /// or a key that is not converted from the other key type
+  InvalidKey { key: String },
+  /// key is not in the list it's looked up from
+  KeyNotFound { key: String },
+}
+
+impl Display for BisectionKeyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::InvalidChar { ch, position } => {
+        write!(
+          f,
+          "invalid character for bisection key: {:?} at {}",
+          ch, position
+        )
+      }
+      Self::DigitOutOfRange { digit, position } => {
+        write!(
+          f,
+          "digit out of range for bisection key: {} at {}",
+          digit, position
+        )
+      }
+      Self::EqualKeys { left, right } => write!(f, "cannot bisect equal keys: {} {}", left, right),
+      Self::ReversedBounds { left, right } => {
+        write!(
+          f,
+          "left bound is larger than right bound: {} {}",
+          left, right
+        )
+      }
+      Self::NoKeyBetween { left, right } => {
+        write!(
+          f,
+          "not found property way of generating middle key: {} {}",
+          left, right
+        )
+      }
+      Self::NoSpaceToPromote { digits } => write!(f, "not found position to promote: {:?}", digits),
+      Self::IndexOutOfRange { index, len } => write!(f, "index out of range: {} of {}", index, len),
+      Self::InvalidBytes { position } => write!(f, "invalid ordered bytes at {}", position),
+      Self::UnsupportedVersion { version } => {
+        write!(f, "unsupported version of ordered bytes: {}", version)
+      }
+      Self::TrailingMinimum { key } => {
+        write!(f, "trailing 0 is invalid during bisect_beginning: {}", key)
+      }
+      Self::InvalidKey { key } => write!(f, "invalid order key: {}", key),
+      Self::KeyNotFound { key } => write!(f, "key not found in list: {}", key),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BisectionKeyError {}
@@ -0,0 +1,329 @@
+//! Keys in the format of the `fractional-indexing` package by rocicorp,
+//! for interoperating with tools using that format.
+//!
+//! A key is an integer part followed by a fraction part, both in `Base62` digits:
+//!
+//! ```text
+//! a0    a1    a0V    Zz    b125
+//! ```
+//!
+//! The head character of the integer part tells its length, `a` to `z` for 2 to 27 characters,
+//! `Z` down to `A` for 2 to 27 characters of negative integers.
+//! The fraction part never ends with `0`. Keys are ordered as plain strings.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::{Base62, BisectionKeyError, Charset, FractionalKey};
+
+const ZERO: u8 = b'0';
+const LAST: u8 = b'z';
+
+/// smallest integer part, `A` followed by 26 zeros, which has nothing before it
+const SMALLEST_INTEGER: &[u8; 27] = b"A00000000000000000000000000";
+
+/// create it like:
+/// ```rust
+/// let _ = bisection_key::FractionalIndexKey::new("a0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FractionalIndexKey(String);
+
+impl Default for FractionalIndexKey {
+  fn default() -> Self {
+    Self::initial()
+  }
+}
+
+impl Display for FractionalIndexKey {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl FromStr for FractionalIndexKey {
+  type Err = BisectionKeyError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    for (position, ch) in s.chars().enumerate() {
+      if Base62::digit_of(ch).is_none() {
+        return Err(BisectionKeyError::InvalidChar { ch, position });
+      }
+    }
+    let key = s.as_bytes();
+    let size = integer_length(key)?;
+    if key == SMALLEST_INTEGER || (key.len() > size && key.last() == Some(&ZERO)) {
+      return Err(BisectionKeyError::InvalidKey { key: s.to_string() });
+    }
+    Ok(Self(s.to_string()))
+  }
+}
+
+impl FractionalKey for FractionalIndexKey {
+  fn initial() -> Self {
+    Self::of(vec![b'a', ZERO])
+  }
+
+  fn before(&self) -> Result<Self, BisectionKeyError> {
+    Self::between(None, Some(self))
+  }
+
+  fn after(&self) -> Result<Self, BisectionKeyError> {
+    Self::between(Some(self), None)
+  }
+
+  fn between(left: Option<&Self>, right: Option<&Self>) -> Result<Self, BisectionKeyError> {
+    Self::between(left, right)
+  }
+
+  fn n_between(
+    left: Option<&Self>,
+    right: Option<&Self>,
+    n: usize,
+  ) -> Result<Vec<Self>, BisectionKeyError> {
+    Self::n_between(left, right, n)
+  }
+}
+
+impl FractionalIndexKey {
+  pub fn new(s: &str) -> Result<Self, BisectionKeyError> {
+    s.parse()
+  }
+
+  /// characters are checked by callers
+  fn of(chars: Vec<u8>) -> Self {
+    Self(String::from_utf8(chars).expect("keys are made of ASCII characters"))
+  }
+
+  /// same as `generateKeyBetween`, `None` for an open side
+  pub fn between(left: Option<&Self>, right: Option<&Self>) -> Result<Self, BisectionKeyError> {
+    let a = left.map(|k| k.0.as_bytes());
+    let b = right.map(|k| k.0.as_bytes());
+    Ok(Self::of(key_between(a, b)?))
+  }
+
+  /// same as `generateNKeysBetween`, keys are generated by bisecting recursively
+  pub fn n_between(
+    left: Option<&Self>,
+    right: Option<&Self>,
+    n: usize,
+  ) -> Result<Vec<Self>, BisectionKeyError> {
+    match n {
+      0 => return Ok(vec![]),
+      1 => return Ok(vec![Self::between(left, right)?]),
+      _ => {}
+    }
+    match (left, right) {
+      (_, None) => {
+        let mut c = Self::between(left, None)?;
+        let mut ys = vec![c.clone()];
+        for _ in 1..n {
+          c = Self::between(Some(&c), None)?;
+          ys.push(c.clone());
+        }
+        Ok(ys)
+      }
+      (None, Some(_)) => {
+        let mut c = Self::between(None, right)?;
+        let mut ys = vec![c.clone()];
+        for _ in 1..n {
+          c = Self::between(None, Some(&c))?;
+          ys.push(c.clone());
+        }
+        ys.reverse();
+        Ok(ys)
+      }
+      (Some(_), Some(_)) => {
+        let half = n / 2;
+        let c = Self::between(left, right)?;
+        let mut ys = Self::n_between(left, Some(&c), half)?;
+        ys.push(c.clone());
+        ys.extend(Self::n_between(Some(&c), right, n - half - 1)?);
+        Ok(ys)
+      }
+    }
+  }
+}
+
+fn key_between(a: Option<&[u8]>, b: Option<&[u8]>) -> Result<Vec<u8>, BisectionKeyError> {
+  match (a, b) {
+    (None, None) => Ok(vec![b'a', ZERO]),
+    (None, Some(b)) => {
+      let (ib, fb) = b.split_at(integer_length(b)?);
+      if ib == SMALLEST_INTEGER {
+        let mut ys = ib.to_vec();
+        ys.extend(midpoint(&[], Some(fb)));
+        Ok(ys)
+      } else if !fb.is_empty() {
+        Ok(ib.to_vec())
+      } else {
+        decrement_integer(ib).ok_or_else(|| no_key_between(&[], b))
+      }
+    }
+    (Some(a), None) => {
+      let (ia, fa) = a.split_at(integer_length(a)?);
+      match increment_integer(ia) {
+        Some(i) => Ok(i),
+        None => {
+          let mut ys = ia.to_vec();
+          ys.extend(midpoint(fa, None));
+          Ok(ys)
+        }
+      }
+    }
+    (Some(a), Some(b)) => {
+      if a == b {
+        return Err(BisectionKeyError::EqualKeys {
+          left: text(a),
+          right: text(b),
+        });
+      } else if a > b {
+        return Err(BisectionKeyError::ReversedBounds {
+          left: text(a),
+          right: text(b),
+        });
+      }
+      let (ia, fa) = a.split_at(integer_length(a)?);
+      let (ib, fb) = b.split_at(integer_length(b)?);
+      let mut ys = ia.to_vec();
+      if ia == ib {
+        ys.extend(midpoint(fa, Some(fb)));
+        return Ok(ys);
+      }
+      let i = increment_integer(ia).ok_or_else(|| no_key_between(a, b))?;
+      if i.as_slice() < b {
+        Ok(i)
+      } else {
+        ys.extend(midpoint(fa, None));
+        Ok(ys)
+      }
+    }
+  }
+}
+
+/// fraction between two fractions, `None` for no upper bound.
+/// Expects `a < b` and no trailing zeros, which are checked when keys are parsed.
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+  if let Some(b) = b {
+    // common prefix, with `a` padded by zeros
+    let n = b
+      .iter()
+      .enumerate()
+      .take_while(|(idx, x)| a.get(*idx).unwrap_or(&ZERO) == *x)
+      .count();
+    if n > 0 {
+      let mut ys = b[..n].to_vec();
+      ys.extend(midpoint(a.get(n..).unwrap_or(&[]), Some(&b[n..])));
+      return ys;
+    }
+  }
+  let digit_a = a.first().map_or(0, |x| digit(*x));
+  let digit_b = match b {
+    Some(b) => b.first().map_or(0, |x| digit(*x)),
+    None => Base62::SIZE,
+  };
+  if digit_b as i16 - digit_a as i16 > 1 {
+    // rounds half up, like `Math.round`
+    vec![Base62::char_of((digit_a + digit_b).div_ceil(2)) as u8]
+  } else {
+    match b {
+      Some(b) if b.len() > 1 => b[..1].to_vec(),
+      _ => {
+        let mut ys = vec![Base62::char_of(digit_a) as u8];
+        ys.extend(midpoint(a.get(1..).unwrap_or(&[]), None));
+        ys
+      }
+    }
+  }
+}
+
+fn increment_integer(x: &[u8]) -> Option<Vec<u8>> {
+  let head = x[0];
+  let mut ys = x.to_vec();
+  for idx in (1..ys.len()).rev() {
+    if ys[idx] == LAST {
+      ys[idx] = ZERO;
+    } else {
+      ys[idx] = Base62::char_of(digit(ys[idx]) + 1) as u8;
+      return Some(ys);
+    }
+  }
+  // carried past the head, integer part grows or shrinks
+  ys.remove(0);
+  match head {
+    b'Z' => Some(vec![b'a', ZERO]),
+    b'z' => None,
+    _ => {
+      let h = head + 1;
+      if h > b'a' {
+        ys.push(ZERO);
+      } else {
+        ys.pop();
+      }
+      ys.insert(0, h);
+      Some(ys)
+    }
+  }
+}
+
+fn decrement_integer(x: &[u8]) -> Option<Vec<u8>> {
+  let head = x[0];
+  let mut ys = x.to_vec();
+  for idx in (1..ys.len()).rev() {
+    if ys[idx] == ZERO {
+      ys[idx] = LAST;
+    } else {
+      ys[idx] = Base62::char_of(digit(ys[idx]) - 1) as u8;
+      return Some(ys);
+    }
+  }
+  // borrowed past the head, integer part grows or shrinks
+  ys.remove(0);
+  match head {
+    b'a' => Some(vec![b'Z', LAST]),
+    b'A' => None,
+    _ => {
+      let h = head - 1;
+      if h < b'Z' {
+        ys.push(LAST);
+      } else {
+        ys.pop();
+      }
+      ys.insert(0, h);
+      Some(ys)
+    }
+  }
+}
+
+/// length of the integer part, told by the head character
+fn integer_length(key: &[u8]) -> Result<usize, BisectionKeyError> {
+  let size = match key.first() {
+    Some(head @ b'a'..=b'z') => (head - b'a') as usize + 2,
+    Some(head @ b'A'..=b'Z') => (b'Z' - head) as usize + 2,
+    _ => return Err(BisectionKeyError::InvalidKey { key: text(key) }),
+  };
+  if size > key.len() {
+    Err(BisectionKeyError::InvalidKey { key: text(key) })
+  } else {
+    Ok(size)
+  }
+}
+
+fn digit(x: u8) -> u8 {
+  Base62::digit_of(x as char).expect("characters of keys are checked when parsed")
+}
+
+fn text(key: &[u8]) -> String {
+  String::from_utf8_lossy(key).into_owned()
+}
+
+fn no_key_between(a: &[u8], b: &[u8]) -> BisectionKeyError {
+  BisectionKeyError::NoKeyBetween {
+    left: text(a),
+    right: text(b),
+  }
+}
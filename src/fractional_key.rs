@@ -0,0 +1,39 @@
+//! Shared interface of the key types, for lists and storages written once for all of them.
+
+use alloc::vec::Vec;
+
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::BisectionKeyError;
+
+/// Ordered keys that always have room before, after and between each other.
+///
+/// ```rust
+/// use bisection_key::{FractionalKey, LexiconKey};
+///
+/// let first: LexiconKey = LexiconKey::initial();
+/// let last = first.after().unwrap();
+/// let mid = LexiconKey::between(Some(&first), Some(&last)).unwrap();
+/// assert!(first < mid && mid < last);
+/// ```
+pub trait FractionalKey: Ord + Clone + Display + FromStr<Err = BisectionKeyError> {
+  /// key for the first item of an empty list
+  fn initial() -> Self;
+
+  /// a key smaller than `self`
+  fn before(&self) -> Result<Self, BisectionKeyError>;
+
+  /// a key larger than `self`
+  fn after(&self) -> Result<Self, BisectionKeyError>;
+
+  /// a key between two neighbours, `None` for an open side
+  fn between(left: Option<&Self>, right: Option<&Self>) -> Result<Self, BisectionKeyError>;
+
+  /// `n` sorted keys between two neighbours, spread evenly
+  fn n_between(
+    left: Option<&Self>,
+    right: Option<&Self>,
+    n: usize,
+  ) -> Result<Vec<Self>, BisectionKeyError>;
+}
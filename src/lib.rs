@@ -1,6 +1,54 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod balanced_key;
+mod charset;
+mod convert;
+pub mod crdt;
+mod digits;
+mod error;
+mod fractional_index_key;
+mod fractional_key;
 mod lexicon_key;
+mod ordered_bytes;
+mod ordered_list;
+mod packed_key;
+mod rebalance;
+mod reorder;
+#[cfg(feature = "serde")]
+mod serde;
+mod spread;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use balanced_key::BalancedKey;
 
+pub use charset::{Base36, Base62, Base65, Base94, Charset};
+
+pub use convert::{balanced_to_lexicon, lexicon_to_balanced, restore_balanced, restore_lexicon};
+
+pub use error::BisectionKeyError;
+
+pub use fractional_index_key::FractionalIndexKey;
+
+pub use fractional_key::FractionalKey;
+
 pub use lexicon_key::LexiconKey;
+
+pub use ordered_bytes::ORDERED_BYTES_VERSION;
+
+pub use ordered_list::{Change, OrderedList};
+
+pub use packed_key::PackedKey;
+
+pub use rebalance::{rebalance, rebalance_window};
+
+pub use reorder::reorder_keys;
+
+#[cfg(feature = "rusqlite")]
+pub use sqlite::{register_balanced_key_collation, BALANCED_KEY_COLLATION};
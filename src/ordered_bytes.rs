@@ -0,0 +1,117 @@
+//! Byte encoding of keys whose `memcmp` order equals the order of keys,
+//! for storing keys directly as keys of embedded KV stores like sled or RocksDB.
+//! The format is documented on `ORDERED_BYTES_VERSION`.
+
+use alloc::vec::Vec;
+
+use crate::digits::Digits;
+use crate::{BisectionKeyError, Charset};
+
+/// current version of the byte format, written as the first byte:
+///
+/// - `LexiconKey`, every digit `d` is written as `d + 1`.
+///   Shorter prefixes are smaller in both orders, so nothing else is needed.
+/// - `BalancedKey`, trailing middle digits are stripped first, since `a` equals `aT`.
+///   Digits below the middle digit `M` are written as `d + 1`, digits above as `d + 3`.
+///   A middle digit is written as `M + 1` when the next digit that is not `M` is smaller than `M`,
+///   otherwise as `M + 3`. Key ends with a terminator byte `M + 2`,
+///   which sorts between keys continuing downwards and keys continuing upwards.
+///
+/// Bytes never contain `0`. The charset is not recorded, decode with the same charset.
+pub const ORDERED_BYTES_VERSION: u8 = 1;
+
+pub(crate) fn encode_lexicon(digits: &[u8]) -> Vec<u8> {
+  let mut ys: Vec<u8> = Vec::with_capacity(digits.len() + 1);
+  ys.push(ORDERED_BYTES_VERSION);
+  ys.extend(digits.iter().map(|d| d + 1));
+  ys
+}
+
+pub(crate) fn decode_lexicon<C: Charset>(bytes: &[u8]) -> Result<Digits, BisectionKeyError> {
+  let body = check_version(bytes)?;
+  let mut digits: Digits = Digits::with_capacity(body.len());
+  for (idx, b) in body.iter().enumerate() {
+    if *b == 0 || *b > C::SIZE {
+      return Err(BisectionKeyError::InvalidBytes { position: idx + 1 });
+    }
+    digits.push(b - 1);
+  }
+  Ok(digits)
+}
+
+pub(crate) fn encode_balanced<C: Charset>(digits: &[u8]) -> Vec<u8> {
+  let size = digits
+    .iter()
+    .rposition(|d| *d != C::MID)
+    .map_or(0, |idx| idx + 1);
+  let digits = &digits[..size];
+  let mut ys: Vec<u8> = Vec::with_capacity(size + 2);
+  ys.push(ORDERED_BYTES_VERSION);
+  for (idx, d) in digits.iter().enumerate() {
+    if *d < C::MID {
+      ys.push(d + 1);
+    } else if *d > C::MID {
+      ys.push(d + 3);
+    } else {
+      // stripped already, so there's always a digit that is not middle later
+      let going_down = digits[idx..]
+        .iter()
+        .find(|x| **x != C::MID)
+        .is_some_and(|x| *x < C::MID);
+      ys.push(if going_down { C::MID + 1 } else { C::MID + 3 });
+    }
+  }
+  ys.push(C::MID + 2);
+  ys
+}
+
+pub(crate) fn decode_balanced<C: Charset>(bytes: &[u8]) -> Result<Digits, BisectionKeyError> {
+  let body = check_version(bytes)?;
+  let size = match body.last() {
+    Some(b) if *b == C::MID + 2 => body.len() - 1,
+    _ => {
+      return Err(BisectionKeyError::InvalidBytes {
+        position: bytes.len(),
+      })
+    }
+  };
+  let mut digits: Digits = Digits::with_capacity(size);
+  // direction promised by middle digits, checked against the next digit that is not middle
+  let mut pending: Option<bool> = None;
+  for (idx, b) in body[..size].iter().enumerate() {
+    let position = idx + 1;
+    let b = *b;
+    if b == 0 || b == C::MID + 2 || b > C::MAX + 3 {
+      return Err(BisectionKeyError::InvalidBytes { position });
+    } else if b == C::MID + 1 || b == C::MID + 3 {
+      let going_down = b == C::MID + 1;
+      if pending.is_some_and(|x| x != going_down) {
+        return Err(BisectionKeyError::InvalidBytes { position });
+      }
+      pending = Some(going_down);
+      digits.push(C::MID);
+    } else {
+      let d = if b <= C::MID { b - 1 } else { b - 3 };
+      if pending.is_some_and(|going_down| going_down != (d < C::MID)) {
+        return Err(BisectionKeyError::InvalidBytes { position });
+      }
+      pending = None;
+      digits.push(d);
+    }
+  }
+  if pending.is_some() {
+    // trailing middle digits should have been stripped
+    return Err(BisectionKeyError::InvalidBytes {
+      position: bytes.len() - 1,
+    });
+  }
+  Ok(digits)
+}
+
+fn check_version(bytes: &[u8]) -> Result<&[u8], BisectionKeyError> {
+  match bytes.first() {
+    Some(&ORDERED_BYTES_VERSION) => Ok(&bytes[1..]),
+    Some(version) => Err(BisectionKeyError::UnsupportedVersion { version: *version }),
+    None => Err(BisectionKeyError::InvalidBytes { position: 0 }),
+  }
+}
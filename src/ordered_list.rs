@@ -0,0 +1,276 @@
+//! A list of values kept in the order of fractional keys. The list picks keys for insertions
+//! and moves, and records every assignment, so that only changed rows are written to storage.
+//!
+//! ```rust
+//! use bisection_key::{Change, LexiconKey, OrderedList};
+//!
+//! let mut list: OrderedList<LexiconKey, &str> = OrderedList::new();
+//! let b = list.push_back("b").unwrap();
+//! list.push_front("a").unwrap();
+//! let c = list.insert_after(&b, "c").unwrap();
+//! assert_eq!(list.values().copied().collect::<Vec<_>>(), ["a", "b", "c"]);
+//! assert_eq!(list.take_changes().len(), 3);
+//!
+//! list.move_to(&c, 0).unwrap();
+//! assert_eq!(list.values().copied().collect::<Vec<_>>(), ["c", "a", "b"]);
+//! assert!(matches!(list.take_changes()[0], Change::Remove(_)));
+//! ```
+
+use alloc::collections::btree_map::{self, BTreeMap};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use core::mem;
+use core::ops::Bound::{Excluded, Unbounded};
+
+use crate::{BisectionKeyError, FractionalKey};
+
+/// an entry of the change log, apply them in order to keep a storage in sync
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Change<K, V> {
+  /// value written at a key, for inserted items, moved items and replaced values
+  Insert(K, V),
+  /// key no longer used, for removed items and old keys of moved items
+  Remove(K),
+}
+
+/// values ordered by keys of type `K`, like `LexiconKey` or `BalancedKey`.
+/// Index-based methods walk the list from the front.
+#[derive(Debug, Clone)]
+pub struct OrderedList<K: FractionalKey, V> {
+  entries: BTreeMap<K, V>,
+  changes: Vec<Change<K, V>>,
+}
+
+impl<K: FractionalKey, V> Default for OrderedList<K, V> {
+  fn default() -> Self {
+    Self {
+      entries: BTreeMap::new(),
+      changes: Vec::new(),
+    }
+  }
+}
+
+/// items loaded from a storage, they are not recorded as changes
+impl<K: FractionalKey, V> FromIterator<(K, V)> for OrderedList<K, V> {
+  fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+    Self {
+      entries: iter.into_iter().collect(),
+      changes: Vec::new(),
+    }
+  }
+}
+
+impl<K: FractionalKey, V> OrderedList<K, V> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn contains_key(&self, key: &K) -> bool {
+    self.entries.contains_key(key)
+  }
+
+  pub fn get(&self, key: &K) -> Option<&V> {
+    self.entries.get(key)
+  }
+
+  pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+    self.entries.iter().nth(index)
+  }
+
+  pub fn index_of(&self, key: &K) -> Option<usize> {
+    if self.entries.contains_key(key) {
+      Some(self.entries.range(..key).count())
+    } else {
+      None
+    }
+  }
+
+  pub fn remove(&mut self, key: &K) -> Option<V> {
+    let value = self.entries.remove(key)?;
+    self.changes.push(Change::Remove(key.clone()));
+    Some(value)
+  }
+
+  pub fn first(&self) -> Option<(&K, &V)> {
+    self.entries.first_key_value()
+  }
+
+  pub fn last(&self) -> Option<(&K, &V)> {
+    self.entries.last_key_value()
+  }
+
+  pub fn iter(&self) -> btree_map::Iter<'_, K, V> {
+    self.entries.iter()
+  }
+
+  pub fn keys(&self) -> btree_map::Keys<'_, K, V> {
+    self.entries.keys()
+  }
+
+  pub fn values(&self) -> btree_map::Values<'_, K, V> {
+    self.entries.values()
+  }
+
+  /// changes since the last call, in the order they were made
+  pub fn changes(&self) -> &[Change<K, V>] {
+    &self.changes
+  }
+
+  /// takes changes out for persisting them, the log starts over empty
+  pub fn take_changes(&mut self) -> Vec<Change<K, V>> {
+    mem::take(&mut self.changes)
+  }
+
+  fn prev_key(&self, key: &K) -> Option<&K> {
+    self.entries.range(..key).next_back().map(|(k, _)| k)
+  }
+
+  fn next_key(&self, key: &K) -> Option<&K> {
+    self
+      .entries
+      .range((Excluded(key), Unbounded))
+      .next()
+      .map(|(k, _)| k)
+  }
+
+  fn check_key(&self, key: &K) -> Result<(), BisectionKeyError> {
+    if self.entries.contains_key(key) {
+      Ok(())
+    } else {
+      Err(BisectionKeyError::KeyNotFound {
+        key: key.to_string(),
+      })
+    }
+  }
+}
+
+impl<K: FractionalKey, V: Clone> OrderedList<K, V> {
+  /// list of values with keys spread evenly, every item is recorded as a change
+  pub fn from_values<T: IntoIterator<Item = V>>(values: T) -> Result<Self, BisectionKeyError> {
+    let values: Vec<V> = values.into_iter().collect();
+    let keys = K::n_between(None, None, values.len())?;
+    let mut list = Self::new();
+    for (key, value) in keys.into_iter().zip(values) {
+      list.put(key, value);
+    }
+    Ok(list)
+  }
+
+  pub fn push_front(&mut self, value: V) -> Result<K, BisectionKeyError> {
+    let key = K::between(None, self.entries.keys().next())?;
+    Ok(self.put(key, value))
+  }
+
+  pub fn push_back(&mut self, value: V) -> Result<K, BisectionKeyError> {
+    let key = K::between(self.entries.keys().next_back(), None)?;
+    Ok(self.put(key, value))
+  }
+
+  pub fn insert_before(&mut self, anchor: &K, value: V) -> Result<K, BisectionKeyError> {
+    self.check_key(anchor)?;
+    let key = K::between(self.prev_key(anchor), Some(anchor))?;
+    Ok(self.put(key, value))
+  }
+
+  pub fn insert_after(&mut self, anchor: &K, value: V) -> Result<K, BisectionKeyError> {
+    self.check_key(anchor)?;
+    let key = K::between(Some(anchor), self.next_key(anchor))?;
+    Ok(self.put(key, value))
+  }
+
+  /// inserts a value so that it ends up at `index`, `len()` for the end
+  pub fn insert_at(&mut self, index: usize, value: V) -> Result<K, BisectionKeyError> {
+    let len = self.entries.len();
+    if index > len {
+      return Err(BisectionKeyError::IndexOutOfRange { index, len });
+    }
+    let mut keys = self.entries.keys().skip(index.saturating_sub(1));
+    let (left, right) = if index == 0 {
+      (None, keys.next())
+    } else {
+      (keys.next(), keys.next())
+    };
+    let key = K::between(left, right)?;
+    Ok(self.put(key, value))
+  }
+
+  /// replaces the value at `key`, returns the old value
+  pub fn set(&mut self, key: &K, value: V) -> Result<V, BisectionKeyError> {
+    self.check_key(key)?;
+    let old = self.entries.insert(key.clone(), value.clone());
+    self.changes.push(Change::Insert(key.clone(), value));
+    Ok(old.expect("checked above"))
+  }
+
+  /// moves an item right before `anchor`, returns its new key.
+  /// Moving between two neighbours `y` and `z` is the same as moving before `z`.
+  pub fn move_before(&mut self, key: &K, anchor: &K) -> Result<K, BisectionKeyError> {
+    self.check_key(key)?;
+    self.check_key(anchor)?;
+    let prev = self.prev_key(anchor);
+    if key == anchor || prev == Some(key) {
+      // already in place
+      return Ok(key.clone());
+    }
+    let new_key = K::between(prev, Some(anchor))?;
+    Ok(self.relocate(key, new_key))
+  }
+
+  /// moves an item right after `anchor`, returns its new key
+  pub fn move_after(&mut self, key: &K, anchor: &K) -> Result<K, BisectionKeyError> {
+    self.check_key(key)?;
+    self.check_key(anchor)?;
+    let next = self.next_key(anchor);
+    if key == anchor || next == Some(key) {
+      // already in place
+      return Ok(key.clone());
+    }
+    let new_key = K::between(Some(anchor), next)?;
+    Ok(self.relocate(key, new_key))
+  }
+
+  /// moves an item so that it ends up at `index`, returns its new key
+  pub fn move_to(&mut self, key: &K, index: usize) -> Result<K, BisectionKeyError> {
+    self.check_key(key)?;
+    let len = self.entries.len();
+    if index >= len {
+      return Err(BisectionKeyError::IndexOutOfRange { index, len });
+    }
+    if self.index_of(key) == Some(index) {
+      return Ok(key.clone());
+    }
+    // neighbours at the new position, among other items
+    let mut others = self.entries.keys().filter(|k| *k != key);
+    let (left, right) = if index == 0 {
+      (None, others.next())
+    } else {
+      let mut others = others.skip(index - 1);
+      (others.next(), others.next())
+    };
+    let new_key = K::between(left, right)?;
+    Ok(self.relocate(key, new_key))
+  }
+
+  fn put(&mut self, key: K, value: V) -> K {
+    self
+      .changes
+      .push(Change::Insert(key.clone(), value.clone()));
+    self.entries.insert(key.clone(), value);
+    key
+  }
+
+  fn relocate(&mut self, key: &K, new_key: K) -> K {
+    let value = self.entries.remove(key).expect("checked by callers");
+    self.changes.push(Change::Remove(key.clone()));
+    self.put(new_key, value)
+  }
+}
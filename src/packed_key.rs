@@ -0,0 +1,206 @@
+//! Keys packed into as few bits per digit as the charset needs,
+//! like 6 bits for `Base36` and 7 bits for `Base65`, for holding lots of keys in memory.
+//!
+//! Digits are first written as ordered bytes(see `ORDERED_BYTES_VERSION`, without the version byte),
+//! then every byte is packed into `bits` bits, most significant bit first,
+//! and the last byte is padded with `0`s. Ordered bytes never contain `0`,
+//! so padding sorts before any digit and marks the end, and packed keys are compared as bytes.
+//!
+//! Trailing `0` bytes are dropped, they compare like the end of a key. Packed bytes that fit
+//! in `INLINE_PACKED` bytes, 9 digits of 7 bits, are kept inline and padded with `0`s,
+//! longer ones go to the heap, so a `PackedKey` takes 16 bytes instead of 24 of a key.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::marker::PhantomData;
+
+use crate::ordered_bytes::ORDERED_BYTES_VERSION;
+use crate::{BalancedKey, Charset, LexiconKey};
+
+/// a key in packed form, compared without unpacking.
+/// `K` is the key type it's packed from, `LexiconKey` or `BalancedKey`.
+///
+/// ```rust
+/// use bisection_key::{LexiconKey, PackedKey};
+///
+/// let k = LexiconKey::new("aT3").unwrap();
+/// let packed = PackedKey::from(&k);
+/// assert_eq!(packed.as_bytes().len(), 3);
+/// assert_eq!(LexiconKey::from(&packed), k);
+/// ```
+pub struct PackedKey<K = LexiconKey>(Packed, PhantomData<K>);
+
+/// bytes kept inline, as many as fit beside the pointer niche of `Box<[u8]>`
+const INLINE_PACKED: usize = 8;
+
+#[derive(Clone)]
+enum Packed {
+  /// padded with `0`s
+  Inline([u8; INLINE_PACKED]),
+  /// longer than `INLINE_PACKED`, never ends with `0`
+  Spilled(Box<[u8]>),
+}
+
+impl<K> PackedKey<K> {
+  fn of(mut bytes: Vec<u8>) -> Self {
+    let size = bytes.iter().rposition(|b| *b != 0).map_or(0, |idx| idx + 1);
+    bytes.truncate(size);
+    let packed = if size <= INLINE_PACKED {
+      let mut xs = [0; INLINE_PACKED];
+      xs[..size].copy_from_slice(&bytes);
+      Packed::Inline(xs)
+    } else {
+      Packed::Spilled(bytes.into_boxed_slice())
+    };
+    Self(packed, PhantomData)
+  }
+
+  /// packed bytes without trailing `0`s, their order matches the order of keys
+  pub fn as_bytes(&self) -> &[u8] {
+    match &self.0 {
+      Packed::Inline(xs) => {
+        let size = xs.iter().rposition(|b| *b != 0).map_or(0, |idx| idx + 1);
+        &xs[..size]
+      }
+      Packed::Spilled(xs) => xs,
+    }
+  }
+
+  /// whether packed bytes are too long to be kept inline
+  pub fn spilled(&self) -> bool {
+    matches!(self.0, Packed::Spilled(_))
+  }
+}
+
+impl<K> Clone for PackedKey<K> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone(), PhantomData)
+  }
+}
+
+impl<K> Debug for PackedKey<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("PackedKey").field(&self.as_bytes()).finish()
+  }
+}
+
+impl<K> Eq for PackedKey<K> {}
+
+/// equal keys are always packed into the same bytes
+impl<K> PartialEq for PackedKey<K> {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl<K> Ord for PackedKey<K> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    match (&self.0, &other.0) {
+      // padding compares like the end of bytes
+      (Packed::Inline(xs), Packed::Inline(ys)) => xs.cmp(ys),
+      _ => self.as_bytes().cmp(other.as_bytes()),
+    }
+  }
+}
+
+impl<K> PartialOrd for PackedKey<K> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<K> Hash for PackedKey<K> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_bytes().hash(state);
+  }
+}
+
+impl<C: Charset> From<&LexiconKey<C>> for PackedKey<LexiconKey<C>> {
+  fn from(key: &LexiconKey<C>) -> Self {
+    Self::of(pack(&key.to_ordered_bytes()[1..], bits_for(C::SIZE)))
+  }
+}
+
+impl<C: Charset> From<&PackedKey<LexiconKey<C>>> for LexiconKey<C> {
+  fn from(packed: &PackedKey<LexiconKey<C>>) -> Self {
+    let bytes = unpack(packed.as_bytes(), bits_for(C::SIZE));
+    LexiconKey::from_ordered_bytes(&bytes).expect("packed from a valid key")
+  }
+}
+
+impl<C: Charset> From<&BalancedKey<C>> for PackedKey<BalancedKey<C>> {
+  fn from(key: &BalancedKey<C>) -> Self {
+    Self::of(pack(&key.to_ordered_bytes()[1..], bits_for(C::MAX + 3)))
+  }
+}
+
+impl<C: Charset> From<&PackedKey<BalancedKey<C>>> for BalancedKey<C> {
+  fn from(packed: &PackedKey<BalancedKey<C>>) -> Self {
+    let bytes = unpack(packed.as_bytes(), bits_for(C::MAX + 3));
+    BalancedKey::from_ordered_bytes(&bytes).expect("packed from a valid key")
+  }
+}
+
+impl<C: Charset> Display for PackedKey<LexiconKey<C>> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", LexiconKey::from(self))
+  }
+}
+
+impl<C: Charset> Display for PackedKey<BalancedKey<C>> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", BalancedKey::from(self))
+  }
+}
+
+/// bits needed for bytes up to `max`
+const fn bits_for(max: u8) -> u32 {
+  u8::BITS - max.leading_zeros()
+}
+
+fn pack(bytes: &[u8], bits: u32) -> Vec<u8> {
+  let mut ys: Vec<u8> = Vec::with_capacity((bytes.len() * bits as usize).div_ceil(8));
+  let mut acc: u32 = 0;
+  let mut filled: u32 = 0;
+  for b in bytes {
+    acc = (acc << bits) | *b as u32;
+    filled += bits;
+    while filled >= 8 {
+      filled -= 8;
+      ys.push((acc >> filled) as u8);
+    }
+    acc &= (1 << filled) - 1;
+  }
+  if filled > 0 {
+    ys.push((acc << (8 - filled)) as u8);
+  }
+  ys
+}
+
+/// ordered bytes with the version byte, stops at padding.
+/// Trailing `0` bytes were dropped, so bytes are read as if followed by `0`s
+fn unpack(packed: &[u8], bits: u32) -> Vec<u8> {
+  let mut ys: Vec<u8> = Vec::with_capacity(packed.len() * 8 / bits as usize + 1);
+  ys.push(ORDERED_BYTES_VERSION);
+  let mut acc: u32 = 0;
+  let mut filled: u32 = 0;
+  for b in packed.iter().chain(iter::repeat(&0)) {
+    acc = (acc << 8) | *b as u32;
+    filled += 8;
+    while filled >= bits {
+      filled -= bits;
+      let x = ((acc >> filled) & ((1 << bits) - 1)) as u8;
+      if x == 0 {
+        return ys;
+      }
+      ys.push(x);
+    }
+    acc &= (1 << filled) - 1;
+  }
+  unreachable!("padding always ends unpacking")
+}
@@ -0,0 +1,63 @@
+//! Reissuing short keys for lists whose keys grew long after many insertions.
+
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{BisectionKeyError, FractionalKey};
+
+/// fresh keys for a whole list, spread evenly with the shortest length.
+/// Keys are assigned by position, so the order of the list is preserved.
+///
+/// ```rust
+/// use bisection_key::{rebalance, LexiconKey};
+///
+/// let keys: Vec<LexiconKey> = ["a", "azzzzzzV", "b"].iter().map(|s| s.parse().unwrap()).collect();
+/// let ys = rebalance(&keys).unwrap();
+/// assert!(ys[0] < ys[1] && ys[1] < ys[2]);
+/// ```
+pub fn rebalance<K: FractionalKey>(keys: &[K]) -> Result<Vec<K>, BisectionKeyError> {
+  K::n_between(None, None, keys.len())
+}
+
+/// rewrites only a contiguous window around keys longer than `max_len` characters,
+/// the window grows one key at a time on either side, and the smallest window
+/// whose new keys fit between its neighbours within `max_len` is picked.
+/// Returns `(index, key)` pairs of the rows that need to be updated.
+///
+/// `keys` are expected to be sorted.
+pub fn rebalance_window<K: FractionalKey>(
+  keys: &[K],
+  max_len: usize,
+) -> Result<Vec<(usize, K)>, BisectionKeyError> {
+  let is_long = |k: &K| k.to_string().chars().count() > max_len;
+  let (lo, hi) = match (
+    keys.iter().position(is_long),
+    keys.iter().rposition(is_long),
+  ) {
+    (Some(lo), Some(hi)) => (lo, hi),
+    _ => return Ok(vec![]),
+  };
+
+  // windows by size, each size tried at every offset around the long keys
+  for extra in 0..keys.len() {
+    for grow_left in 0..=extra {
+      let grow_right = extra - grow_left;
+      if grow_left > lo || hi + grow_right >= keys.len() {
+        continue;
+      }
+      let (start, end) = (lo - grow_left, hi + grow_right);
+      let left = start.checked_sub(1).map(|idx| &keys[idx]);
+      let right = keys.get(end + 1);
+      let is_whole = left.is_none() && right.is_none();
+      match K::n_between(left, right, end - start + 1) {
+        Ok(ys) if is_whole || !ys.iter().any(is_long) => return Ok((start..).zip(ys).collect()),
+        Ok(_) => {}
+        // neighbours too close, try another window
+        Err(BisectionKeyError::NoKeyBetween { .. }) if !is_whole => {}
+        Err(e) => return Err(e),
+      }
+    }
+  }
+  unreachable!("a window of the whole list always fits")
+}
@@ -0,0 +1,81 @@
+//! Keys for a list reordered as a whole, like after dragging items around in a UI,
+//! while rewriting as few rows as possible.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{BisectionKeyError, FractionalKey};
+
+/// new keys for items of `old`, a list of `(id, key)` pairs, reordered into `new_order`.
+/// Items whose keys form the longest increasing run in the new order keep their keys,
+/// only other items get new keys, spread evenly in the gaps between kept keys.
+/// Ids not in `old` are new items and get keys too, ids missing from `new_order` are ignored.
+///
+/// Returns `(id, key)` assignments in the new order. Ids are expected to be unique.
+///
+/// ```rust
+/// use bisection_key::{reorder_keys, LexiconKey};
+///
+/// let old: Vec<(char, LexiconKey)> = ['a', 'b', 'c']
+///   .iter()
+///   .map(|id| (*id, id.to_string().parse().unwrap()))
+///   .collect();
+/// let changes = reorder_keys(&old, &['c', 'a', 'b']).unwrap();
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].0, 'c');
+/// assert!(changes[0].1 < old[0].1);
+/// ```
+pub fn reorder_keys<I: Ord + Clone, K: FractionalKey>(
+  old: &[(I, K)],
+  new_order: &[I],
+) -> Result<Vec<(I, K)>, BisectionKeyError> {
+  let old_keys: BTreeMap<&I, &K> = old.iter().map(|(id, k)| (id, k)).collect();
+  let keys: Vec<Option<&K>> = new_order
+    .iter()
+    .map(|id| old_keys.get(id).copied())
+    .collect();
+  let kept = longest_increasing(&keys);
+
+  let mut ys: Vec<(I, K)> = vec![];
+  let mut left: Option<&K> = None;
+  let mut start = 0;
+  // every kept key closes a gap, and the end of the list closes the last one
+  for end in kept.iter().copied().chain([new_order.len()]) {
+    let right = keys.get(end).copied().flatten();
+    let gap = &new_order[start..end];
+    let fresh = K::n_between(left, right, gap.len())?;
+    ys.extend(gap.iter().cloned().zip(fresh));
+    left = right;
+    start = end + 1;
+  }
+  Ok(ys)
+}
+
+/// indexes of a longest strictly increasing subsequence, skipping `None`s,
+/// by patience sorting in `O(n log n)`
+fn longest_increasing<K: Ord>(keys: &[Option<&K>]) -> Vec<usize> {
+  // `tails[l]` is the index of the smallest last key of increasing runs of length `l + 1`
+  let mut tails: Vec<usize> = vec![];
+  let mut prev: Vec<Option<usize>> = vec![None; keys.len()];
+  for (idx, key) in keys.iter().enumerate() {
+    let Some(key) = key else {
+      continue;
+    };
+    let len = tails.partition_point(|t| keys[*t].expect("only keys are pushed") < *key);
+    prev[idx] = len.checked_sub(1).map(|l| tails[l]);
+    if len == tails.len() {
+      tails.push(idx);
+    } else {
+      tails[len] = idx;
+    }
+  }
+  let mut ys: Vec<usize> = vec![];
+  let mut cursor = tails.last().copied();
+  while let Some(idx) = cursor {
+    ys.push(idx);
+    cursor = prev[idx];
+  }
+  ys.reverse();
+  ys
+}
@@ -0,0 +1,160 @@
+//! Spreads `n` keys evenly between two keys.
+//!
+//! Digits after the common prefix are treated as a number in the base of charset size,
+//! the shortest length `L` that leaves enough room is picked,
+//! and then keys of `L` more digits are placed at equal steps.
+
+use alloc::vec::Vec;
+
+use smallvec::smallvec;
+
+use crate::digits::Digits;
+use crate::Charset;
+
+/// `128^18` still fits in `i128`, larger spans are left to recursive bisecting
+const MAX_SPAN: usize = 18;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Padding {
+  /// missing digits are smaller than any digit, like in `LexiconKey`
+  Lexicon,
+  /// missing digits are filled with middle digits, like in `BalancedKey`
+  Balanced,
+}
+
+impl Padding {
+  fn digit<C: Charset>(self) -> u8 {
+    match self {
+      Padding::Lexicon => 0,
+      Padding::Balanced => C::MID,
+    }
+  }
+}
+
+/// returns `None` when no length up to `MAX_SPAN` digits is enough,
+/// callers are expected to fall back to bisecting.
+pub(crate) fn spread_digits<C: Charset>(
+  left: Option<&[u8]>,
+  right: Option<&[u8]>,
+  n: usize,
+  padding: Padding,
+) -> Option<Vec<Digits>> {
+  let prefix = common_prefix::<C>(left, right, padding)?;
+  let lo = left.map(|xs| xs.get(prefix.len()..).unwrap_or(&[]));
+  let hi = right.map(|xs| xs.get(prefix.len()..).unwrap_or(&[]));
+
+  // keys of Lexicon need a gap of 2, so that trailing `0` digits can be skipped
+  let step_size: i128 = match padding {
+    Padding::Lexicon => 2,
+    Padding::Balanced => 1,
+  };
+  let count = i128::try_from(n).ok()? + 1;
+
+  for size in 1..=MAX_SPAN {
+    let a = lower_value::<C>(lo, size, padding);
+    let b = upper_value::<C>(hi, size, padding);
+    let span = b - a;
+    if span < count.checked_mul(step_size)? {
+      continue;
+    }
+    let quotient = span / count;
+    let remainder = span % count;
+    let mut ys: Vec<Digits> = Vec::with_capacity(n);
+    for i in 1..count {
+      let mut x = a + i * quotient + i.checked_mul(remainder)? / count;
+      if padding == Padding::Lexicon && x % C::SIZE as i128 == 0 {
+        // key ending with `0` digit leaves no room before its extensions
+        x += 1;
+      }
+      let mut digits = prefix.clone();
+      digits.extend(to_digits::<C>(x, size));
+      if padding == Padding::Balanced {
+        while digits.last() == Some(&C::MID) {
+          digits.pop();
+        }
+      }
+      ys.push(digits);
+    }
+    return Some(ys);
+  }
+  None
+}
+
+/// digits shared by both bounds, `None` when both bounds are equal
+fn common_prefix<C: Charset>(
+  left: Option<&[u8]>,
+  right: Option<&[u8]>,
+  padding: Padding,
+) -> Option<Digits> {
+  let (xs, ys) = match (left, right) {
+    (Some(xs), Some(ys)) => (xs, ys),
+    _ => return Some(smallvec![]),
+  };
+  let mut prefix: Digits = smallvec![];
+  match padding {
+    Padding::Lexicon => {
+      for (x, y) in xs.iter().zip(ys) {
+        if x != y {
+          return Some(prefix);
+        }
+        prefix.push(*x);
+      }
+      if xs.len() == ys.len() {
+        None
+      } else {
+        Some(prefix)
+      }
+    }
+    Padding::Balanced => {
+      for idx in 0..xs.len().max(ys.len()) {
+        let x = xs.get(idx).unwrap_or(&C::MID);
+        let y = ys.get(idx).unwrap_or(&C::MID);
+        if x != y {
+          return Some(prefix);
+        }
+        prefix.push(*x);
+      }
+      None
+    }
+  }
+}
+
+/// largest number of `size` digits that is not larger than `lo`
+fn lower_value<C: Charset>(lo: Option<&[u8]>, size: usize, padding: Padding) -> i128 {
+  match lo {
+    None => -1,
+    Some(xs) if xs.len() < size && padding == Padding::Lexicon => {
+      to_value::<C>(xs, size, padding) - 1
+    }
+    Some(xs) => to_value::<C>(xs, size, padding),
+  }
+}
+
+/// smallest number of `size` digits that is not smaller than `hi`
+fn upper_value<C: Charset>(hi: Option<&[u8]>, size: usize, padding: Padding) -> i128 {
+  match hi {
+    None => (C::SIZE as i128).pow(size as u32),
+    Some(xs) if xs.len() > size && padding == Padding::Lexicon => {
+      to_value::<C>(xs, size, padding) + 1
+    }
+    Some(xs) => to_value::<C>(xs, size, padding),
+  }
+}
+
+/// first `size` digits as a number, padded when `xs` is shorter
+fn to_value<C: Charset>(xs: &[u8], size: usize, padding: Padding) -> i128 {
+  let mut v: i128 = 0;
+  for idx in 0..size {
+    v = v * C::SIZE as i128 + *xs.get(idx).unwrap_or(&padding.digit::<C>()) as i128;
+  }
+  v
+}
+
+fn to_digits<C: Charset>(mut x: i128, size: usize) -> Digits {
+  let mut ys: Digits = smallvec![0; size];
+  for idx in (0..size).rev() {
+    ys[idx] = (x % C::SIZE as i128) as u8;
+    x /= C::SIZE as i128;
+  }
+  ys
+}
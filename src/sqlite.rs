@@ -0,0 +1,72 @@
+//! SQLite columns for keys through `rusqlite`, both key types are stored as `TEXT`.
+//!
+//! `LexiconKey` sorts right with the default `BINARY` collation. `BalancedKey` needs
+//! the `BALANCED_KEY` collation, since `a` equals `aT` and `aS` is smaller than `a`:
+//!
+//! ```sql
+//! CREATE TABLE items (position TEXT COLLATE BALANCED_KEY PRIMARY KEY);
+//! SELECT * FROM items ORDER BY position;
+//! ```
+
+use core::cmp::Ordering;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Connection;
+
+use crate::{BalancedKey, Charset, LexiconKey};
+
+/// name of the collation registered by `register_balanced_key_collation`
+pub const BALANCED_KEY_COLLATION: &str = "BALANCED_KEY";
+
+/// registers the `BALANCED_KEY` collation on a connection, comparing texts as `BalancedKey<C>`.
+/// Texts that are not keys go after keys, compared as plain text.
+/// Collations live in connections, register it on every connection opened.
+///
+/// ```rust
+/// use bisection_key::{register_balanced_key_collation, Base65};
+///
+/// let conn = rusqlite::Connection::open_in_memory().unwrap();
+/// register_balanced_key_collation::<Base65>(&conn).unwrap();
+/// ```
+pub fn register_balanced_key_collation<C: Charset>(conn: &Connection) -> rusqlite::Result<()> {
+  conn.create_collation(BALANCED_KEY_COLLATION, compare_balanced::<C>)
+}
+
+fn compare_balanced<C: Charset>(a: &str, b: &str) -> Ordering {
+  match (a.parse::<BalancedKey<C>>(), b.parse::<BalancedKey<C>>()) {
+    (Ok(x), Ok(y)) => x.cmp(&y),
+    (Ok(_), Err(_)) => Ordering::Less,
+    (Err(_), Ok(_)) => Ordering::Greater,
+    (Err(_), Err(_)) => a.cmp(b),
+  }
+}
+
+impl<C: Charset> ToSql for LexiconKey<C> {
+  fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+    Ok(ToSqlOutput::from(self.to_string()))
+  }
+}
+
+impl<C: Charset> FromSql for LexiconKey<C> {
+  fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+    value
+      .as_str()?
+      .parse()
+      .map_err(|e| FromSqlError::Other(Box::new(e)))
+  }
+}
+
+impl<C: Charset> ToSql for BalancedKey<C> {
+  fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+    Ok(ToSqlOutput::from(self.to_string()))
+  }
+}
+
+impl<C: Charset> FromSql for BalancedKey<C> {
+  fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+    value
+      .as_str()?
+      .parse()
+      .map_err(|e| FromSqlError::Other(Box::new(e)))
+  }
+}
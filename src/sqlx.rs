@@ -0,0 +1,72 @@
+//! Postgres columns for keys through `sqlx`, so that `ORDER BY` sorts rows in the order of keys.
+//!
+//! - `LexiconKey` is stored as `TEXT`, compared with `COLLATE "C"` its order matches the order
+//!   of keys for charsets that are `SORT_COMPATIBLE`, which all built-in charsets are.
+//! - `BalancedKey` is stored as `BYTEA` of ordered bytes(see `ORDERED_BYTES_VERSION`), since `a` equals `aT`
+//!   and no text collation orders it right. Decode with the same charset.
+
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::types::Type;
+
+use crate::{BalancedKey, Charset, LexiconKey};
+
+impl<C: Charset> Type<Postgres> for LexiconKey<C> {
+  fn type_info() -> PgTypeInfo {
+    <str as Type<Postgres>>::type_info()
+  }
+
+  fn compatible(ty: &PgTypeInfo) -> bool {
+    <str as Type<Postgres>>::compatible(ty)
+  }
+}
+
+impl<C: Charset> PgHasArrayType for LexiconKey<C> {
+  fn array_type_info() -> PgTypeInfo {
+    <&str as PgHasArrayType>::array_type_info()
+  }
+}
+
+impl<C: Charset> Encode<'_, Postgres> for LexiconKey<C> {
+  fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+    <&str as Encode<Postgres>>::encode(self.to_string().as_str(), buf)
+  }
+}
+
+impl<'r, C: Charset> Decode<'r, Postgres> for LexiconKey<C> {
+  fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+    let s = <&str as Decode<Postgres>>::decode(value)?;
+    Ok(s.parse()?)
+  }
+}
+
+impl<C: Charset> Type<Postgres> for BalancedKey<C> {
+  fn type_info() -> PgTypeInfo {
+    <[u8] as Type<Postgres>>::type_info()
+  }
+
+  fn compatible(ty: &PgTypeInfo) -> bool {
+    <[u8] as Type<Postgres>>::compatible(ty)
+  }
+}
+
+impl<C: Charset> PgHasArrayType for BalancedKey<C> {
+  fn array_type_info() -> PgTypeInfo {
+    <&[u8] as PgHasArrayType>::array_type_info()
+  }
+}
+
+impl<C: Charset> Encode<'_, Postgres> for BalancedKey<C> {
+  fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+    <&[u8] as Encode<Postgres>>::encode(self.to_ordered_bytes().as_slice(), buf)
+  }
+}
+
+impl<'r, C: Charset> Decode<'r, Postgres> for BalancedKey<C> {
+  fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+    let bytes = <&[u8] as Decode<Postgres>>::decode(value)?;
+    Ok(BalancedKey::from_ordered_bytes(bytes)?)
+  }
+}
@@ -0,0 +1,46 @@
+//! Bindings of `LexiconKey` for JavaScript through `wasm-bindgen`, on strings of the default charset.
+//! Errors are thrown as strings of the error message.
+//!
+//! Functions are plain Rust functions as well, so results can be compared with native code directly.
+
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::LexiconKey;
+
+fn parse(s: &str) -> Result<LexiconKey, String> {
+  LexiconKey::new(s).map_err(|e| e.to_string())
+}
+
+/// a key between two keys, same as `LexiconKey::bisect`
+#[wasm_bindgen]
+pub fn bisect(left: &str, right: &str) -> Result<String, String> {
+  let key = parse(left)?.bisect(&parse(right)?);
+  key.map(|k| k.to_string()).map_err(|e| e.to_string())
+}
+
+/// a key after `key`, same as `LexiconKey::bisect_end`
+#[wasm_bindgen(js_name = bisectEnd)]
+pub fn bisect_end(key: &str) -> Result<String, String> {
+  let key = parse(key)?.bisect_end();
+  key.map(|k| k.to_string()).map_err(|e| e.to_string())
+}
+
+/// a key before `key`, same as `LexiconKey::bisect_beginning`
+#[wasm_bindgen(js_name = bisectBeginning)]
+pub fn bisect_beginning(key: &str) -> Result<String, String> {
+  let key = parse(key)?.bisect_beginning();
+  key.map(|k| k.to_string()).map_err(|e| e.to_string())
+}
+
+/// `-1`, `0` or `1`, like a comparator for `Array.prototype.sort`
+#[wasm_bindgen]
+pub fn compare(left: &str, right: &str) -> Result<i32, String> {
+  let ordering = parse(left)?.cmp(&parse(right)?);
+  Ok(match ordering {
+    Ordering::Less => -1,
+    Ordering::Equal => 0,
+    Ordering::Greater => 1,
+  })
+}
@@ -0,0 +1,82 @@
+extern crate bisection_key;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+
+use bisection_key::{BalancedKey, BisectionKeyError, LexiconKey};
+
+struct CountingAllocator;
+
+thread_local! {
+  // counted per thread, since tests run in parallel
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.with(|n| n.set(n.get() + 1));
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.with(|n| n.set(n.get() + 1));
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_of<T>(f: impl FnOnce() -> T) -> (T, usize) {
+  let start = ALLOCATIONS.with(|n| n.get());
+  let result = f();
+  (result, ALLOCATIONS.with(|n| n.get()) - start)
+}
+
+#[test]
+fn short_lexicon_keys_stay_inline() -> Result<(), BisectionKeyError> {
+  let a = LexiconKey::new("aT3")?;
+  let b = LexiconKey::new("aTx")?;
+  let mut buf = String::with_capacity(64);
+
+  let (k, n) = allocations_of(|| a.bisect(&b));
+  assert_eq!(n, 0);
+  let k = k?;
+  assert_eq!(allocations_of(|| a.cmp(&k)).1, 0);
+  assert_eq!(allocations_of(|| a.bisect_end()).1, 0);
+  assert_eq!(allocations_of(|| a.bisect_beginning()).1, 0);
+  assert_eq!(allocations_of(|| k.clone()).1, 0);
+  assert_eq!(allocations_of(|| write!(buf, "{}", k)).1, 0);
+  assert_eq!(allocations_of(|| "aT3".parse::<LexiconKey>()).1, 0);
+
+  // longer keys spill to the heap, and still work
+  let long = LexiconKey::new("zzzzzzzzzzzzzzzzzzzz")?;
+  assert!(allocations_of(|| long.bisect_end()).1 > 0);
+  assert!(long < long.bisect_end()?);
+
+  Ok(())
+}
+
+#[test]
+fn short_balanced_keys_stay_inline() -> Result<(), BisectionKeyError> {
+  let a = BalancedKey::new("aT3")?;
+  let b = BalancedKey::new("aTx")?;
+  let mut buf = String::with_capacity(64);
+
+  let (k, n) = allocations_of(|| a.bisect(&b));
+  assert_eq!(n, 0);
+  let k = k?;
+  assert_eq!(allocations_of(|| a.cmp(&k)).1, 0);
+  assert_eq!(allocations_of(|| a.bisect_end()).1, 0);
+  assert_eq!(allocations_of(|| a.bisect_beginning()).1, 0);
+  assert_eq!(allocations_of(|| k.clone()).1, 0);
+  assert_eq!(allocations_of(|| write!(buf, "{}", k)).1, 0);
+  assert_eq!(allocations_of(|| "aT3".parse::<BalancedKey>()).1, 0);
+
+  Ok(())
+}
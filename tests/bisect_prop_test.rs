@@ -0,0 +1,130 @@
+extern crate bisection_key;
+
+use bisection_key::{BalancedKey, BisectionKeyError, LexiconKey};
+use proptest::prelude::*;
+
+/// keys of any digits, or keys crowded at edges of the charset to hit carrying
+fn key_text() -> impl Strategy<Value = String> {
+  prop_oneof!["[+\\-/0-9A-Za-z]{0,6}", "[+\\-/STUVyz]{0,8}", "[+z]{0,10}",]
+}
+
+/// only `0` digits are left after the common prefix, like `a` and `a++`
+fn lexicon_is_adjacent(lo: &LexiconKey, hi: &LexiconKey) -> bool {
+  let (lo, hi) = (lo.to_string(), hi.to_string());
+  hi.starts_with(&lo) && hi[lo.len()..].chars().all(|c| c == '+')
+}
+
+fn assert_lexicon_valid(k: &LexiconKey) -> Result<(), TestCaseError> {
+  prop_assert_eq!(&LexiconKey::new(&k.to_string()).unwrap(), k);
+  // keys ending with `+` leave no room before their extensions
+  prop_assert!(!k.to_string().ends_with('+'), "{}", k);
+  Ok(())
+}
+
+fn assert_balanced_valid(k: &BalancedKey) -> Result<(), TestCaseError> {
+  prop_assert_eq!(&BalancedKey::new(&k.to_string()).unwrap(), k);
+  Ok(())
+}
+
+proptest! {
+  #![proptest_config(ProptestConfig::with_cases(2000))]
+
+  #[test]
+  fn lexicon_bisect_in_between(a in key_text(), b in key_text()) {
+    let (a, b) = (LexiconKey::new(&a).unwrap(), LexiconKey::new(&b).unwrap());
+    prop_assume!(a != b);
+    let (lo, hi) = if a < b { (&a, &b) } else { (&b, &a) };
+    for (x, y) in [(lo, hi), (hi, lo)] {
+      match x.bisect(y) {
+        Ok(mid) => {
+          prop_assert!(lo < &mid && &mid < hi, "{} < {} < {}", lo, mid, hi);
+          assert_lexicon_valid(&mid)?;
+        }
+        Err(BisectionKeyError::NoKeyBetween { .. }) => {
+          prop_assert!(lexicon_is_adjacent(lo, hi), "{} {}", lo, hi);
+        }
+        Err(e) => prop_assert!(false, "{} {}: {}", x, y, e),
+      }
+    }
+  }
+
+  #[test]
+  fn balanced_bisect_in_between(a in key_text(), b in key_text()) {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    prop_assume!(a != b);
+    let (lo, hi) = if a < b { (&a, &b) } else { (&b, &a) };
+    for (x, y) in [(lo, hi), (hi, lo)] {
+      let mid = x.bisect(y);
+      prop_assert!(mid.is_ok(), "{} {}: {:?}", x, y, mid);
+      let mid = mid.unwrap();
+      prop_assert!(lo < &mid && &mid < hi, "{} < {} < {}", lo, mid, hi);
+      assert_balanced_valid(&mid)?;
+    }
+  }
+
+  #[test]
+  fn bisect_symmetric(a in key_text(), b in key_text()) {
+    let (x, y) = (LexiconKey::new(&a).unwrap(), LexiconKey::new(&b).unwrap());
+    let (xy, yx) = (x.bisect(&y).map(|k| k.to_string()), y.bisect(&x).map(|k| k.to_string()));
+    prop_assert_eq!(xy.is_ok(), yx.is_ok());
+    prop_assert_eq!(xy.ok(), yx.ok());
+    let (x, y) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    let (xy, yx) = (x.bisect(&y).map(|k| k.to_string()), y.bisect(&x).map(|k| k.to_string()));
+    prop_assert_eq!(xy.is_ok(), yx.is_ok());
+    prop_assert_eq!(xy.ok(), yx.ok());
+  }
+
+  #[test]
+  fn bisect_equal_keys(a in key_text()) {
+    let k = LexiconKey::new(&a).unwrap();
+    let is_equal = matches!(k.bisect(&k), Err(BisectionKeyError::EqualKeys { .. }));
+    prop_assert!(is_equal);
+    let k = BalancedKey::new(&a).unwrap();
+    let is_equal = matches!(k.bisect(&k), Err(BisectionKeyError::EqualKeys { .. }));
+    prop_assert!(is_equal);
+  }
+
+  #[test]
+  fn lexicon_bisect_ends(a in key_text()) {
+    let k = LexiconKey::new(&a).unwrap();
+    let next = k.bisect_end().unwrap();
+    prop_assert!(k < next, "{} < {}", k, next);
+    assert_lexicon_valid(&next)?;
+    match k.bisect_beginning() {
+      Ok(prev) => {
+        prop_assert!(prev < k, "{} < {}", prev, k);
+        assert_lexicon_valid(&prev)?;
+      }
+      Err(BisectionKeyError::TrailingMinimum { .. }) => {
+        prop_assert!(a.chars().all(|c| c == '+'), "{}", a);
+      }
+      Err(e) => prop_assert!(false, "{}: {}", k, e),
+    }
+  }
+
+  #[test]
+  fn balanced_bisect_ends(a in key_text()) {
+    let k = BalancedKey::new(&a).unwrap();
+    let next = k.bisect_end().unwrap();
+    prop_assert!(k < next, "{} < {}", k, next);
+    assert_balanced_valid(&next)?;
+    let prev = k.bisect_beginning().unwrap();
+    prop_assert!(prev < k, "{} < {}", prev, k);
+    assert_balanced_valid(&prev)?;
+  }
+
+  #[test]
+  fn n_between_in_order(a in key_text(), b in key_text(), n in 0..40usize) {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    prop_assume!(a != b);
+    let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+    let xs = BalancedKey::n_between(Some(&lo), Some(&hi), n).unwrap();
+    prop_assert_eq!(xs.len(), n);
+    let mut ys = vec![lo.clone()];
+    ys.extend(xs);
+    ys.push(hi.clone());
+    for pair in ys.windows(2) {
+      prop_assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+    }
+  }
+}
@@ -0,0 +1,105 @@
+extern crate bisection_key;
+
+use bisection_key::{
+  BalancedKey, Base36, Base62, Base65, Base94, BisectionKeyError, Charset, FractionalKey,
+  LexiconKey,
+};
+
+/// same patterns as tests of the default charset, run for keys of every charset
+fn check_insertions<K: FractionalKey>(start: &str, end: &str) -> Result<(), BisectionKeyError> {
+  let mut k = K::initial();
+  for _ in 0..1000 {
+    let next = k.before()?;
+    assert!(next < k);
+    k = next;
+  }
+
+  let mut k = K::initial();
+  for _ in 0..1000 {
+    let next = k.after()?;
+    assert!(k < next);
+    k = next;
+  }
+
+  let mut left: K = start.parse()?;
+  let mut right: K = end.parse()?;
+  for i in 0..1000 {
+    let next = K::between(Some(&left), Some(&right))?;
+    assert!(left < next, "{} < {}", left, next);
+    assert!(next < right, "{} < {}", next, right);
+    if i % 3 == 0 {
+      right = next;
+    } else {
+      left = next;
+    }
+  }
+
+  let left: K = start.parse()?;
+  let right: K = end.parse()?;
+  let xs = K::n_between(Some(&left), Some(&right), 300)?;
+  assert!(left < xs[0]);
+  assert!(xs[299] < right);
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1]);
+  }
+
+  Ok(())
+}
+
+fn check_charset<C: Charset>(start: &str, end: &str) -> Result<(), BisectionKeyError> {
+  check_insertions::<LexiconKey<C>>(start, end)?;
+  check_insertions::<BalancedKey<C>>(start, end)?;
+
+  let k: LexiconKey<C> = FractionalKey::initial();
+  assert_eq!(k.to_string(), C::char_of(C::MID).to_string());
+  assert_eq!(k.to_string().parse::<LexiconKey<C>>()?, k);
+  Ok(())
+}
+
+#[test]
+fn test_charsets() -> Result<(), BisectionKeyError> {
+  check_charset::<Base36>("a", "b")?;
+  check_charset::<Base62>("a", "b")?;
+  check_charset::<Base65>("a", "b")?;
+  check_charset::<Base94>("a", "b")
+}
+
+#[test]
+fn test_charset_properties() {
+  assert_eq!((Base36::SIZE, Base36::MAX, Base36::MID), (36, 35, 17));
+  assert_eq!((Base62::SIZE, Base62::MAX, Base62::MID), (62, 61, 30));
+  assert_eq!((Base65::SIZE, Base65::MAX, Base65::MID), (65, 64, 32));
+  assert_eq!((Base94::SIZE, Base94::MAX, Base94::MID), (94, 93, 46));
+  assert!(sort_compatible::<Base36>());
+  assert!(sort_compatible::<Base62>());
+  assert!(sort_compatible::<Base65>());
+  assert!(sort_compatible::<Base94>());
+  assert!(!sort_compatible::<Reversed>());
+}
+
+fn sort_compatible<C: Charset>() -> bool {
+  C::SORT_COMPATIBLE
+}
+
+/// a custom charset, with characters not in ASCII order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Reversed;
+
+impl Charset for Reversed {
+  const CHARS: &'static str = "zyxwvutsrqponmlkjihgfedcba";
+}
+
+#[test]
+fn test_custom_charset() -> Result<(), BisectionKeyError> {
+  check_charset::<Reversed>("k", "j")
+}
+
+#[test]
+fn test_lexicon_matches_string_order() -> Result<(), BisectionKeyError> {
+  let xs = LexiconKey::<Base94>::n_between(None, None, 200)?;
+  for pair in xs.windows(2) {
+    assert!(pair[0].to_string() < pair[1].to_string());
+  }
+  assert!("A".parse::<LexiconKey<Base36>>().is_err());
+  Ok(())
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "cli")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn run(args: &[&str], stdin: &str) -> (bool, String) {
+  run_bytes(args, stdin.as_bytes())
+}
+
+fn run_bytes(args: &[&str], stdin: &[u8]) -> (bool, String) {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_bisection-key"))
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(stdin).unwrap();
+  let output = child.wait_with_output().unwrap();
+  (
+    output.status.success(),
+    String::from_utf8(output.stdout).unwrap(),
+  )
+}
+
+fn lines(args: &[&str], stdin: &str) -> Vec<String> {
+  let (ok, out) = run(args, stdin);
+  assert!(ok, "{:?}", args);
+  out.lines().map(|s| s.to_owned()).collect()
+}
+
+#[test]
+fn generating_keys() {
+  assert_eq!(lines(&["between", "a", "b"], ""), ["a1"]);
+  assert_eq!(lines(&["--balanced", "between", "a", "aU"], ""), ["aTV"]);
+  assert_eq!(lines(&["between", "", ""], ""), ["T"]);
+  assert_eq!(lines(&["after", "a"], ""), ["c"]);
+  assert_eq!(lines(&["before", "-a"], ""), ["+v"]);
+  assert_eq!(lines(&["spread", "", "", "--count", "5"], "").len(), 5);
+  assert!(!run(&["between", "b", "a"], "").0);
+  // lexicon keys are the default, there's no flag for them
+  assert!(!run(&["--lexicon", "after", "a"], "").0);
+}
+
+#[test]
+fn inspecting_keys() {
+  assert_eq!(lines(&["compare", "a", "aT"], ""), ["<"]);
+  assert_eq!(lines(&["--balanced", "compare", "a", "aT"], ""), ["="]);
+  assert_eq!(lines(&["validate", "a", "b"], ""), Vec::<String>::new());
+  let (ok, out) = run(&["validate"], "a\na!\n");
+  assert!(!ok);
+  assert!(out.starts_with("a!\t"));
+}
+
+#[test]
+fn rebalancing_keys() {
+  let ys = lines(&["rebalance"], "a\nazzzzzzzzV\n\nb\n");
+  assert_eq!(ys.len(), 3);
+  assert!(ys[0] < ys[1] && ys[1] < ys[2]);
+  assert!(ys.iter().all(|k| k.len() == 1));
+}
+
+#[test]
+fn unreadable_stdin() {
+  // invalid UTF-8 in the middle fails, instead of rebalancing the first key alone
+  let (ok, out) = run_bytes(&["rebalance"], b"a\n\xff\nb\n");
+  assert!(!ok);
+  assert!(out.is_empty());
+  assert!(!run_bytes(&["validate"], b"a\n\xff\n").0);
+}
+
+#[test]
+fn closed_stdout() {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_bisection-key"))
+    .args(["spread", "", "", "--count", "1000000"])
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  // reads a line and closes the pipe, like `head -n 1`
+  let mut line = String::new();
+  BufReader::new(child.stdout.take().unwrap())
+    .read_line(&mut line)
+    .unwrap();
+  assert!(!line.is_empty());
+  let output = child.wait_with_output().unwrap();
+  assert!(output.status.success());
+  assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,127 @@
+extern crate bisection_key;
+
+use bisection_key::{
+  balanced_to_lexicon, lexicon_to_balanced, restore_balanced, restore_lexicon, BalancedKey, Base36,
+  BisectionKeyError, LexiconKey,
+};
+use proptest::prelude::*;
+
+fn assert_sorted<K: Ord + std::fmt::Display>(xs: &[K]) {
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+  }
+}
+
+fn texts<K: ToString>(xs: &[K]) -> Vec<String> {
+  xs.iter().map(|k| k.to_string()).collect()
+}
+
+#[test]
+fn convert_differently_ordered() -> Result<(), BisectionKeyError> {
+  // `a` equals `aT` in `BalancedKey`, and `aS` is smaller than `a`
+  let xs: Vec<BalancedKey> = ["+", "aS", "aT+", "a", "aTz", "aU", "b", "zzz"]
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<_, _>>()?;
+  let ys = balanced_to_lexicon(&xs);
+  assert_eq!(
+    texts(&ys),
+    ["+TT", "aSTT", "aT++TT", "aTT", "aTzzTT", "aUTT", "bTT", "zzzTT"]
+  );
+  assert_sorted(&ys);
+
+  let zs = lexicon_to_balanced(&ys);
+  assert_eq!(texts(&zs)[..3], ["+zTT+", "aSTT+", "aT+z+zTT+"]);
+  assert_sorted(&zs);
+
+  let empty: Vec<LexiconKey> = balanced_to_lexicon(&[]);
+  assert!(empty.is_empty());
+
+  Ok(())
+}
+
+#[test]
+fn convert_single_keys() -> Result<(), BisectionKeyError> {
+  // equal keys convert into the same key
+  let a = LexiconKey::from(&BalancedKey::new("a")?);
+  assert_eq!(a, LexiconKey::from(&BalancedKey::new("aTT")?));
+  assert_eq!(a.to_string(), "aTT");
+
+  let xs = [LexiconKey::new("a")?, LexiconKey::new("a+")?];
+  let ys = lexicon_to_balanced(&xs);
+  assert_eq!(texts(&ys), ["a+", "a+z+"]);
+  assert_sorted(&ys);
+
+  Ok(())
+}
+
+#[test]
+fn restore_invalid() -> Result<(), BisectionKeyError> {
+  // no terminator, a middle digit without direction, and a wrong direction
+  for s in ["a", "aTa", "aT+zTT", "aTTa"] {
+    let k = LexiconKey::new(s)?;
+    assert_eq!(
+      restore_balanced(&k),
+      Err(BisectionKeyError::InvalidKey { key: s.to_owned() })
+    );
+  }
+  // no terminator, and an escaped `0` without `MAX`
+  for s in ["a", "a+a+"] {
+    let k = BalancedKey::new(s)?;
+    assert_eq!(
+      restore_lexicon(&k),
+      Err(BisectionKeyError::InvalidKey { key: s.to_owned() })
+    );
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn balanced_round_trip(a in "[+\\-/0-9A-Za-z]{0,12}") {
+    let k: BalancedKey = a.parse().unwrap();
+    prop_assert_eq!(restore_balanced(&LexiconKey::from(&k)), Ok(k));
+  }
+
+  #[test]
+  fn lexicon_round_trip(a in "[+\\-/0-9A-Za-z]{0,12}") {
+    let k: LexiconKey = a.parse().unwrap();
+    prop_assert_eq!(restore_lexicon(&BalancedKey::from(&k)), Ok(k));
+  }
+
+  #[test]
+  fn balanced_keys_keep_order(a in "[+\\-/0-9A-Za-z]{0,8}", b in "[+\\-/0-9A-Za-z]{0,8}") {
+    let (x, y): (BalancedKey, BalancedKey) = (a.parse().unwrap(), b.parse().unwrap());
+    let (lx, ly) = (LexiconKey::from(&x), LexiconKey::from(&y));
+    prop_assert_eq!(lx.cmp(&ly), x.cmp(&y));
+    // sorting by plain strings matches too, which is what SQL `ORDER BY` does
+    prop_assert_eq!(lx.to_string().cmp(&ly.to_string()), x.cmp(&y));
+    // the last digit that is not middle is followed by the terminator
+    let last = a.trim_end_matches('T');
+    let ending = format!("{}TT", &last[last.len().saturating_sub(1)..]);
+    prop_assert!(lx.to_string().ends_with(&ending), "{} {}", lx, ending);
+  }
+
+  #[test]
+  fn lexicon_keys_keep_order(a in "[0-9a-z]{0,8}", b in "[0-9a-z]{0,8}") {
+    let (x, y): (LexiconKey<Base36>, LexiconKey<Base36>) = (a.parse().unwrap(), b.parse().unwrap());
+    let (bx, by) = (BalancedKey::from(&x), BalancedKey::from(&y));
+    prop_assert_eq!(bx.cmp(&by), x.cmp(&y));
+    prop_assert_eq!(bx.to_string(), format!("{}0", a.replace('0', "0z")));
+    // and back again
+    prop_assert_eq!(LexiconKey::from(&bx).cmp(&LexiconKey::from(&by)), x.cmp(&y));
+  }
+
+  #[test]
+  fn balanced_lists_keep_order(names in prop::collection::vec("[+\\-/0-9A-Za-z]{1,8}", 0..200)) {
+    let mut xs: Vec<BalancedKey> = names.iter().map(|s| s.parse().unwrap()).collect();
+    xs.sort();
+    xs.dedup();
+    let ys = balanced_to_lexicon(&xs);
+    prop_assert_eq!(ys.len(), xs.len());
+    assert_sorted(&ys);
+    for (x, y) in xs.iter().zip(&ys) {
+      prop_assert_eq!(y, &LexiconKey::from(x));
+    }
+  }
+}
@@ -0,0 +1,319 @@
+extern crate bisection_key;
+
+use bisection_key::crdt::{ItemId, Op, Position, ReplicatedList};
+use bisection_key::{BisectionKeyError, LexiconKey};
+use proptest::prelude::*;
+
+const REPLICAS: usize = 3;
+
+/// a step of a replica, numbers are reduced into indexes when the step is run
+#[derive(Debug, Clone)]
+enum Step {
+  Insert(usize, usize),
+  Delete(usize, usize),
+  Move(usize, usize, usize),
+  /// applies an operation from the inbox, picked by position, so delivery is out of order
+  Deliver(usize, usize),
+  /// applies an operation that was applied already
+  Redeliver(usize, usize),
+}
+
+fn step() -> impl Strategy<Value = Step> {
+  let replica = 0..REPLICAS;
+  prop_oneof![
+    3 => (replica.clone(), any::<usize>()).prop_map(|(r, i)| Step::Insert(r, i)),
+    1 => (replica.clone(), any::<usize>()).prop_map(|(r, i)| Step::Delete(r, i)),
+    2 => (replica.clone(), any::<usize>(), any::<usize>()).prop_map(|(r, i, j)| Step::Move(r, i, j)),
+    4 => (replica.clone(), any::<usize>()).prop_map(|(r, i)| Step::Deliver(r, i)),
+    1 => (replica, any::<usize>()).prop_map(|(r, i)| Step::Redeliver(r, i)),
+  ]
+}
+
+struct Network {
+  replicas: Vec<ReplicatedList<u32>>,
+  inboxes: Vec<Vec<Op<u32>>>,
+  delivered: Vec<Vec<Op<u32>>>,
+  next_value: u32,
+}
+
+impl Network {
+  fn new() -> Self {
+    Self {
+      replicas: (0..REPLICAS)
+        .map(|r| ReplicatedList::new(r as u64 + 1))
+        .collect(),
+      inboxes: vec![vec![]; REPLICAS],
+      delivered: vec![vec![]; REPLICAS],
+      next_value: 0,
+    }
+  }
+
+  fn broadcast(&mut self, from: usize, op: Op<u32>) {
+    for (r, inbox) in self.inboxes.iter_mut().enumerate() {
+      if r != from {
+        inbox.push(op.clone());
+      }
+    }
+  }
+
+  fn run(&mut self, step: &Step) -> Result<(), BisectionKeyError> {
+    match *step {
+      Step::Insert(r, i) => {
+        let index = i % (self.replicas[r].len() + 1);
+        self.next_value += 1;
+        let op = self.replicas[r].insert(index, self.next_value)?;
+        assert_eq!(self.replicas[r].get(index), Some(&self.next_value));
+        self.broadcast(r, op);
+      }
+      Step::Delete(r, i) => {
+        let len = self.replicas[r].len();
+        if len > 0 {
+          let op = self.replicas[r].delete(i % len).unwrap();
+          self.broadcast(r, op);
+        }
+      }
+      Step::Move(r, i, j) => {
+        let len = self.replicas[r].len();
+        if len > 0 {
+          let value = self.replicas[r].get(i % len).copied();
+          let op = self.replicas[r].move_item(i % len, j % len)?;
+          assert_eq!(self.replicas[r].get(j % len).copied(), value);
+          self.broadcast(r, op);
+        }
+      }
+      Step::Deliver(r, i) => {
+        if !self.inboxes[r].is_empty() {
+          let idx = i % self.inboxes[r].len();
+          let op = self.inboxes[r].remove(idx);
+          self.replicas[r].apply(op.clone());
+          self.delivered[r].push(op);
+        }
+      }
+      Step::Redeliver(r, i) => {
+        if !self.delivered[r].is_empty() {
+          let op = self.delivered[r][i % self.delivered[r].len()].clone();
+          self.replicas[r].apply(op);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// delivers everything left, newest first to make it even more out of order
+  fn flush(&mut self) {
+    for r in 0..REPLICAS {
+      while let Some(op) = self.inboxes[r].pop() {
+        self.replicas[r].apply(op);
+      }
+    }
+  }
+
+  fn orders(&self) -> Vec<Vec<u32>> {
+    self
+      .replicas
+      .iter()
+      .map(|list| list.values().copied().collect())
+      .collect()
+  }
+}
+
+proptest! {
+  #![proptest_config(ProptestConfig::with_cases(500))]
+
+  #[test]
+  fn replicas_converge(steps in prop::collection::vec(step(), 0..120)) {
+    let mut network = Network::new();
+    for s in &steps {
+      network.run(s).unwrap();
+    }
+    network.flush();
+    let orders = network.orders();
+    for order in &orders[1..] {
+      prop_assert_eq!(order, &orders[0]);
+    }
+  }
+
+  #[test]
+  fn merging_states_converges(steps in prop::collection::vec(step(), 0..120)) {
+    let mut network = Network::new();
+    for s in &steps {
+      network.run(s).unwrap();
+    }
+    // merging full states in both directions, instead of delivering operations
+    let mut a = network.replicas[0].clone();
+    let mut b = network.replicas[1].clone();
+    a.merge(&network.replicas[2]);
+    a.merge(&b);
+    b.merge(&network.replicas[2]);
+    b.merge(&a);
+    let (xs, ys): (Vec<u32>, Vec<u32>) = (a.values().copied().collect(), b.values().copied().collect());
+    prop_assert_eq!(xs, ys);
+  }
+}
+
+#[test]
+fn concurrent_inserts_at_same_place() -> Result<(), BisectionKeyError> {
+  let mut alice = ReplicatedList::new(1);
+  let mut bob = ReplicatedList::new(2);
+  let x = alice.insert(0, 'x')?;
+  bob.apply(x.clone());
+  let a = alice.insert(1, 'a')?;
+  let b = bob.insert(1, 'b')?;
+  alice.apply(b);
+  bob.apply(a);
+  let order: String = alice.values().collect();
+  assert_eq!(order, bob.values().collect::<String>());
+  assert!(order == "xab" || order == "xba", "{}", order);
+
+  // room is left between items of different replicas
+  let c = alice.insert(2, 'c')?;
+  bob.apply(c);
+  assert_eq!(
+    alice.values().collect::<String>(),
+    bob.values().collect::<String>()
+  );
+  assert_eq!(alice.get(2), Some(&'c'));
+
+  Ok(())
+}
+
+#[test]
+fn delete_wins_over_move() -> Result<(), BisectionKeyError> {
+  let mut alice = ReplicatedList::new(1);
+  let mut bob = ReplicatedList::new(2);
+  let mut inserts = vec![];
+  for (i, v) in "abc".chars().enumerate() {
+    let op = alice.insert(i, v)?;
+    bob.apply(op.clone());
+    inserts.push(op);
+  }
+  let moved = alice.move_item(0, 2)?;
+  let deleted = bob.delete(0).unwrap();
+  alice.apply(deleted.clone());
+  bob.apply(moved.clone());
+  assert_eq!(alice.values().collect::<String>(), "bc");
+  assert_eq!(bob.values().collect::<String>(), "bc");
+
+  // a move or an insertion arriving after the delete doesn't bring the item back
+  let mut carol = ReplicatedList::new(3);
+  carol.apply(deleted);
+  carol.apply(moved);
+  carol.apply(inserts[0].clone());
+  assert!(carol.is_empty());
+
+  Ok(())
+}
+
+#[test]
+fn latest_move_wins() -> Result<(), BisectionKeyError> {
+  let mut alice = ReplicatedList::new(1);
+  let mut bob = ReplicatedList::new(2);
+  let mut inserts = vec![];
+  for (i, v) in "abcd".chars().enumerate() {
+    let op = alice.insert(i, v)?;
+    bob.apply(op.clone());
+    inserts.push(op);
+  }
+  let first = alice.move_item(0, 3)?;
+  bob.apply(first.clone());
+  let second = bob.move_item(3, 1)?;
+  alice.apply(second.clone());
+  assert_eq!(alice.values().collect::<String>(), "bacd");
+
+  // an older move arriving late is ignored
+  let mut carol = ReplicatedList::new(3);
+  carol.apply(second);
+  carol.apply(first);
+  for op in inserts {
+    carol.apply(op);
+  }
+  assert_eq!(carol.values().collect::<String>(), "bacd");
+
+  Ok(())
+}
+
+/// a value that can't be cloned, like a handle to a resource
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+fn insert_op(replica: u64, key: &str, value: u32) -> Result<Op<Handle>, BisectionKeyError> {
+  Ok(Op::Insert {
+    id: ItemId {
+      replica,
+      counter: 1,
+    },
+    position: Position {
+      key: LexiconKey::new(key)?,
+      replica,
+      counter: 1,
+    },
+    value: Handle(value),
+  })
+}
+
+#[test]
+fn values_without_clone() -> Result<(), BisectionKeyError> {
+  let mut list = ReplicatedList::new(9);
+  for (replica, key) in [(1, "a"), (2, "b"), (3, "c")] {
+    list.apply(insert_op(replica, key, replica as u32)?);
+  }
+  assert_eq!(list.len(), 3);
+  assert_eq!(list.get(0), Some(&Handle(1)));
+
+  let moved = list.move_item(0, 2)?;
+  assert!(matches!(moved, Op::Move { .. }));
+  let deleted = list.delete(0).unwrap();
+  assert!(matches!(deleted, Op::Delete { .. }));
+  assert_eq!(list.values().collect::<Vec<_>>(), [&Handle(3), &Handle(1)]);
+
+  Ok(())
+}
+
+fn insert_at_key(replica: u64, key: &str, value: char) -> Result<Op<char>, BisectionKeyError> {
+  Ok(Op::Insert {
+    id: ItemId {
+      replica,
+      counter: 1,
+    },
+    position: Position {
+      key: LexiconKey::new(key)?,
+      replica,
+      counter: 1,
+    },
+    value,
+  })
+}
+
+#[test]
+fn inserting_between_equal_keys() -> Result<(), BisectionKeyError> {
+  // items of replicas 1 and 3 created concurrently with the same key
+  let ops = [
+    insert_at_key(1, "a", 'x')?,
+    insert_at_key(3, "a", 'y')?,
+    insert_at_key(4, "b", 'z')?,
+  ];
+
+  // replica 2 sorts between them, so the item goes right at the index
+  let mut list = ReplicatedList::new(2);
+  for op in ops.iter().cloned() {
+    list.apply(op);
+  }
+  list.insert(1, 'w')?;
+  assert_eq!(list.values().collect::<String>(), "xwyz");
+
+  // replica 5 sorts after both, so the item goes after all items of the key
+  let mut list = ReplicatedList::new(5);
+  for op in ops.iter().cloned() {
+    list.apply(op);
+  }
+  list.insert(1, 'w')?;
+  assert_eq!(list.values().collect::<String>(), "xywz");
+
+  // moving between equal keys again, the moving item is skipped when looking for the bound
+  list.move_item(2, 1)?;
+  assert_eq!(list.values().collect::<String>(), "xywz");
+  list.move_item(3, 1)?;
+  assert_eq!(list.values().collect::<String>(), "xyzw");
+
+  Ok(())
+}
@@ -0,0 +1,130 @@
+extern crate bisection_key;
+
+use bisection_key::{BisectionKeyError, FractionalIndexKey, FractionalKey};
+
+fn key_between(a: Option<&str>, b: Option<&str>) -> Result<String, BisectionKeyError> {
+  let a = a.map(FractionalIndexKey::new).transpose()?;
+  let b = b.map(FractionalIndexKey::new).transpose()?;
+  Ok(FractionalIndexKey::between(a.as_ref(), b.as_ref())?.to_string())
+}
+
+fn n_keys_between(a: Option<&str>, b: Option<&str>, n: usize) -> Result<String, BisectionKeyError> {
+  let a = a.map(FractionalIndexKey::new).transpose()?;
+  let b = b.map(FractionalIndexKey::new).transpose()?;
+  let xs = FractionalIndexKey::n_between(a.as_ref(), b.as_ref(), n)?;
+  Ok(
+    xs.iter()
+      .map(|k| k.to_string())
+      .collect::<Vec<_>>()
+      .join(" "),
+  )
+}
+
+/// test vectors of `generateKeyBetween` from the `fractional-indexing` package
+#[test]
+fn reference_key_between() -> Result<(), BisectionKeyError> {
+  let cases = [
+    (None, None, "a0"),
+    (None, Some("a0"), "Zz"),
+    (None, Some("Zz"), "Zy"),
+    (Some("a0"), None, "a1"),
+    (Some("a1"), None, "a2"),
+    (Some("a0"), Some("a1"), "a0V"),
+    (Some("a1"), Some("a2"), "a1V"),
+    (Some("a0V"), Some("a1"), "a0l"),
+    (Some("Zz"), Some("a0"), "ZzV"),
+    (Some("Zz"), Some("a1"), "a0"),
+    (None, Some("Y00"), "Xzzz"),
+    (Some("bzz"), None, "c000"),
+    (Some("a0"), Some("a0V"), "a0G"),
+    (Some("a0"), Some("a0G"), "a08"),
+    (Some("b125"), Some("b129"), "b127"),
+    (Some("a0"), Some("a1V"), "a1"),
+    (Some("Zz"), Some("a01"), "a0"),
+    (None, Some("a0V"), "a0"),
+    (None, Some("b999"), "b99"),
+    (
+      None,
+      Some("A000000000000000000000000001"),
+      "A000000000000000000000000000V",
+    ),
+    (
+      Some("zzzzzzzzzzzzzzzzzzzzzzzzzzy"),
+      None,
+      "zzzzzzzzzzzzzzzzzzzzzzzzzzz",
+    ),
+    (
+      Some("zzzzzzzzzzzzzzzzzzzzzzzzzzz"),
+      None,
+      "zzzzzzzzzzzzzzzzzzzzzzzzzzzV",
+    ),
+  ];
+  for (a, b, expected) in cases {
+    assert_eq!(key_between(a, b)?, expected, "{:?} {:?}", a, b);
+  }
+  Ok(())
+}
+
+#[test]
+fn reference_errors() {
+  let invalid = |key: &str| BisectionKeyError::InvalidKey {
+    key: key.to_owned(),
+  };
+  assert_eq!(
+    key_between(None, Some("A00000000000000000000000000")),
+    Err(invalid("A00000000000000000000000000"))
+  );
+  assert_eq!(key_between(Some("a00"), None), Err(invalid("a00")));
+  assert_eq!(key_between(Some("a00"), Some("a1")), Err(invalid("a00")));
+  assert_eq!(key_between(Some("0"), Some("1")), Err(invalid("0")));
+  assert_eq!(key_between(Some("b1"), None), Err(invalid("b1")));
+  assert_eq!(
+    key_between(Some("a1"), Some("a0")),
+    Err(BisectionKeyError::ReversedBounds {
+      left: String::from("a1"),
+      right: String::from("a0")
+    })
+  );
+  assert_eq!(
+    key_between(Some("a!"), None),
+    Err(BisectionKeyError::InvalidChar {
+      ch: '!',
+      position: 1
+    })
+  );
+}
+
+/// test vectors of `generateNKeysBetween` from the `fractional-indexing` package
+#[test]
+fn reference_n_keys_between() -> Result<(), BisectionKeyError> {
+  assert_eq!(n_keys_between(None, None, 5)?, "a0 a1 a2 a3 a4");
+  assert_eq!(
+    n_keys_between(Some("a4"), None, 10)?,
+    "a5 a6 a7 a8 a9 aA aB aC aD aE"
+  );
+  assert_eq!(n_keys_between(None, Some("a0"), 5)?, "Zv Zw Zx Zy Zz");
+  assert_eq!(
+    n_keys_between(Some("a0"), Some("a2"), 20)?,
+    "a04 a08 a0G a0K a0O a0V a0Z a0d a0l a0t a1 a14 a18 a1G a1O a1V a1Z a1d a1l a1t"
+  );
+  assert_eq!(n_keys_between(Some("a0"), Some("a2"), 0)?, "");
+  Ok(())
+}
+
+#[test]
+fn as_fractional_key() -> Result<(), BisectionKeyError> {
+  let first = FractionalIndexKey::initial();
+  assert_eq!(first, FractionalIndexKey::default());
+  let last = first.after()?;
+  let mid = FractionalIndexKey::between(Some(&first), Some(&last))?;
+  assert!(first < mid && mid < last);
+  assert!(first.before()? < first);
+
+  let mut k = first;
+  for _ in 0..1000 {
+    let next = k.before()?;
+    assert!(next < k);
+    k = next;
+  }
+  Ok(())
+}
@@ -0,0 +1,57 @@
+extern crate bisection_key;
+
+use bisection_key::{BalancedKey, BisectionKeyError, FractionalKey, LexiconKey};
+
+/// keeps inserting at front, back and middle of a list, with code shared by key types
+fn fill_list<K: FractionalKey>() -> Result<Vec<K>, BisectionKeyError> {
+  let mut xs: Vec<K> = vec![K::between(None, None)?];
+  for i in 0..300 {
+    let (idx, key) = match i % 3 {
+      0 => (0, K::between(None, xs.first())?),
+      1 => (xs.len(), K::between(xs.last(), None)?),
+      _ => {
+        let idx = xs.len() / 2;
+        (idx, K::between(xs.get(idx - 1), xs.get(idx))?)
+      }
+    };
+    xs.insert(idx, key);
+  }
+  Ok(xs)
+}
+
+fn assert_sorted<K: FractionalKey>(xs: &[K]) {
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+  }
+}
+
+#[test]
+fn test_generic_list() -> Result<(), BisectionKeyError> {
+  assert_sorted(&fill_list::<LexiconKey>()?);
+  assert_sorted(&fill_list::<BalancedKey>()?);
+  Ok(())
+}
+
+#[test]
+fn test_before_after() -> Result<(), BisectionKeyError> {
+  let k: LexiconKey = LexiconKey::initial();
+  assert!(k.before()? < k);
+  assert!(k < k.after()?);
+
+  let k: BalancedKey = BalancedKey::initial();
+  assert!(k.before()? < k);
+  assert!(k < k.after()?);
+  Ok(())
+}
+
+#[test]
+fn test_parse_and_clone() -> Result<(), BisectionKeyError> {
+  let k: LexiconKey = "aV".parse()?;
+  assert_eq!(k.clone(), k);
+  assert_eq!(k.to_string(), "aV");
+
+  let k: BalancedKey = "aV".parse()?;
+  assert_eq!(k.clone(), k);
+  assert!("a!".parse::<BalancedKey>().is_err());
+  Ok(())
+}
@@ -0,0 +1,52 @@
+extern crate bisection_key;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use bisection_key::{BalancedKey, LexiconKey};
+use proptest::prelude::*;
+
+fn hash_of<T: Hash>(x: &T) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  x.hash(&mut hasher);
+  hasher.finish()
+}
+
+proptest! {
+  #[test]
+  fn lexicon_equal_keys_share_hash(a in "[+TUaz]{0,4}", b in "[+TUaz]{0,4}") {
+    let (a, b) = (LexiconKey::new(&a).unwrap(), LexiconKey::new(&b).unwrap());
+    if a == b {
+      prop_assert_eq!(hash_of(&a), hash_of(&b));
+    }
+  }
+
+  #[test]
+  fn balanced_equal_keys_share_hash(a in "[+TUaz]{0,4}", b in "[+TUaz]{0,4}") {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    if a == b {
+      prop_assert_eq!(hash_of(&a), hash_of(&b));
+    }
+  }
+
+  #[test]
+  fn balanced_trailing_middle_share_hash(a in "[+\\-/0-9A-Za-z]{0,8}", padding in 0..6usize) {
+    let b = format!("{}{}", a, "T".repeat(padding));
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    prop_assert_eq!(&a, &b);
+    prop_assert_eq!(hash_of(&a), hash_of(&b));
+  }
+}
+
+#[test]
+fn keys_in_hash_map() {
+  let mut dict: HashMap<BalancedKey, &str> = HashMap::new();
+  dict.insert(BalancedKey::new("a").unwrap(), "a");
+  assert_eq!(dict.get(&BalancedKey::new("aTT").unwrap()), Some(&"a"));
+
+  let mut dict: HashMap<LexiconKey, &str> = HashMap::new();
+  dict.insert(LexiconKey::new("a").unwrap(), "a");
+  assert_eq!(dict.get(&LexiconKey::new("aT").unwrap()), None);
+  assert_eq!(dict.get(&LexiconKey::new("a").unwrap()), Some(&"a"));
+}
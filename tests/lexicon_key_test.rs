@@ -1,6 +1,6 @@
 extern crate bisection_key;
 
-use bisection_key::LexiconKey;
+use bisection_key::{BisectionKeyError, LexiconKey};
 
 #[test]
 fn equality_of_keys() {
@@ -20,7 +20,40 @@ fn order_of_keys() {
 }
 
 #[test]
-fn test_insertion_beginning() -> Result<(), String> {
+fn errors_of_keys() -> Result<(), BisectionKeyError> {
+  assert_eq!(
+    LexiconKey::new("a!b"),
+    Err(BisectionKeyError::InvalidChar {
+      ch: '!',
+      position: 1
+    })
+  );
+  assert_eq!(
+    LexiconKey::new("a")?.bisect(&LexiconKey::new("a")?),
+    Err(BisectionKeyError::EqualKeys {
+      left: String::from("a"),
+      right: String::from("a")
+    })
+  );
+  assert_eq!(
+    LexiconKey::new("a")?.bisect(&LexiconKey::new("a+")?),
+    Err(BisectionKeyError::NoKeyBetween {
+      left: String::from("a"),
+      right: String::from("a+")
+    })
+  );
+  assert_eq!(
+    LexiconKey::new("++")?.bisect_beginning(),
+    Err(BisectionKeyError::TrailingMinimum {
+      key: String::from("++")
+    })
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_insertion_beginning() -> Result<(), BisectionKeyError> {
   let mut k = LexiconKey::default();
   for _ in 0..1000 {
     let next = k.bisect_beginning()?;
@@ -32,7 +65,7 @@ fn test_insertion_beginning() -> Result<(), String> {
 }
 
 #[test]
-fn test_insertion_end() -> Result<(), String> {
+fn test_insertion_end() -> Result<(), BisectionKeyError> {
   let mut k = LexiconKey::default();
   for _ in 0..1000 {
     let next = k.bisect_end()?;
@@ -44,7 +77,7 @@ fn test_insertion_end() -> Result<(), String> {
 }
 
 #[test]
-fn test_insertion_middle() -> Result<(), String> {
+fn test_insertion_middle() -> Result<(), BisectionKeyError> {
   let mut left = LexiconKey::new("a")?;
   let right = LexiconKey::new("b")?;
   for _ in 0..1000 {
@@ -83,3 +116,129 @@ fn test_insertion_middle() -> Result<(), String> {
 
   Ok(())
 }
+
+#[test]
+fn test_n_between() -> Result<(), BisectionKeyError> {
+  let left = LexiconKey::new("a")?;
+  let right = LexiconKey::new("b")?;
+  let xs = LexiconKey::n_between(Some(&left), Some(&right), 500)?;
+  assert_eq!(xs.len(), 500);
+  assert!(left < xs[0]);
+  assert!(xs[499] < right);
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1]);
+  }
+  // 2 more digits are enough for 500 keys
+  assert!(xs.iter().all(|k| k.to_string().len() <= 3));
+
+  let xs: Vec<LexiconKey> = LexiconKey::n_between(None, None, 10)?;
+  assert!(xs.iter().all(|k| k.to_string().len() == 1));
+  let xs = LexiconKey::n_between(Some(&left), None, 100)?;
+  assert!(left < xs[0]);
+  let xs = LexiconKey::n_between(None, Some(&right), 100)?;
+  assert!(xs[99] < right);
+  assert!(LexiconKey::n_between(Some(&left), Some(&right), 0)?.is_empty());
+
+  // long carrying of `z`s, spread by bisecting
+  let far = LexiconKey::new("azzzzzzzzzzzzzzzzzzzzzzzzzzzz")?;
+  let xs = LexiconKey::n_between(Some(&far), Some(&right), 50)?;
+  assert!(far < xs[0]);
+  assert!(xs[49] < right);
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1]);
+  }
+
+  Ok(())
+}
+
+#[test]
+fn test_between() -> Result<(), BisectionKeyError> {
+  let a = LexiconKey::new("a")?;
+  let b = LexiconKey::new("b")?;
+  assert_eq!(LexiconKey::between(None, None)?, LexiconKey::default());
+  assert!(LexiconKey::between(Some(&a), None)? > a);
+  assert!(LexiconKey::between(None, Some(&a))? < a);
+  let mid = LexiconKey::between(Some(&a), Some(&b))?;
+  assert!(a < mid && mid < b);
+
+  assert_eq!(
+    LexiconKey::between(Some(&b), Some(&a)),
+    Err(BisectionKeyError::ReversedBounds {
+      left: String::from("b"),
+      right: String::from("a")
+    })
+  );
+  assert_eq!(
+    LexiconKey::between(Some(&a), Some(&LexiconKey::new("a")?)),
+    Err(BisectionKeyError::EqualKeys {
+      left: String::from("a"),
+      right: String::from("a")
+    })
+  );
+  assert!(LexiconKey::n_between(Some(&b), Some(&a), 3).is_err());
+
+  Ok(())
+}
+
+#[test]
+fn test_bisect_unique() -> Result<(), BisectionKeyError> {
+  for (a, b) in [
+    ("a", "b"),
+    ("a", "aT"),
+    ("T", "T-"),
+    ("z", "zz"),
+    ("a+V", "a"),
+    ("+", "++V"),
+  ] {
+    let (a, b) = (LexiconKey::new(a)?, LexiconKey::new(b)?);
+    let (lo, hi) = if a < b { (&a, &b) } else { (&b, &a) };
+    let mut xs: Vec<LexiconKey> = vec![];
+    for client_id in (0..300).chain([u64::MAX]) {
+      let k = a.bisect_unique(&b, client_id)?;
+      assert!(lo < &k && &k < hi, "{} < {} < {}", lo, k, hi);
+      assert!(k.to_string().len() < 20);
+      xs.push(k);
+    }
+    xs.sort();
+    for pair in xs.windows(2) {
+      assert!(pair[0] != pair[1]);
+    }
+  }
+
+  Ok(())
+}
+
+#[test]
+fn test_bisect_borrowing() -> Result<(), BisectionKeyError> {
+  for (a, b) in [("-S", "+-"), ("", "-z"), ("+-", "-S")] {
+    let (a, b) = (LexiconKey::new(a)?, LexiconKey::new(b)?);
+    let (lo, hi) = if a < b { (&a, &b) } else { (&b, &a) };
+    let k = a.bisect(&b)?;
+    assert!(lo < &k && &k < hi, "{} < {} < {}", lo, k, hi);
+    assert!(!k.to_string().ends_with('+'), "{}", k);
+  }
+
+  Ok(())
+}
+
+#[test]
+fn test_bisect_either_order() -> Result<(), BisectionKeyError> {
+  for (a, b) in [
+    ("a", "b"),
+    ("a", "aT"),
+    ("-S", "+-"),
+    ("zz", "zzz"),
+    ("Tzzzz", "U"),
+  ] {
+    let (a, b) = (LexiconKey::new(a)?, LexiconKey::new(b)?);
+    assert_eq!(a.bisect(&b)?.to_string(), b.bisect(&a)?.to_string());
+  }
+  assert_eq!(
+    LexiconKey::new("b")?
+      .bisect(&LexiconKey::new("a")?)?
+      .to_string(),
+    "a1"
+  );
+
+  Ok(())
+}
@@ -0,0 +1,120 @@
+extern crate bisection_key;
+
+use bisection_key::{
+  BalancedKey, Base36, Base65, BisectionKeyError, Charset, LexiconKey, ORDERED_BYTES_VERSION,
+};
+use proptest::prelude::*;
+
+/// every key of `Base36` with at most 2 digits, including the empty key
+fn all_short_keys() -> Vec<String> {
+  let chars: Vec<char> = Base36::CHARS.chars().collect();
+  let mut xs = vec![String::new()];
+  for a in &chars {
+    xs.push(a.to_string());
+    for b in &chars {
+      xs.push(format!("{}{}", a, b));
+    }
+  }
+  xs
+}
+
+#[test]
+fn lexicon_order_preserved_exhaustively() -> Result<(), BisectionKeyError> {
+  let keys: Vec<LexiconKey<Base36>> = all_short_keys()
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<_, _>>()?;
+  let bytes: Vec<Vec<u8>> = keys.iter().map(|k| k.to_ordered_bytes()).collect();
+  for (x, bx) in keys.iter().zip(&bytes) {
+    assert_eq!(&LexiconKey::<Base36>::from_ordered_bytes(bx)?, x);
+    for (y, by) in keys.iter().zip(&bytes) {
+      assert_eq!(x.cmp(y), bx.cmp(by), "{} {}", x, y);
+    }
+  }
+  Ok(())
+}
+
+#[test]
+fn balanced_order_preserved_exhaustively() -> Result<(), BisectionKeyError> {
+  let mut names = all_short_keys();
+  // trailing middle digits, which are equal to shorter keys
+  names.extend(
+    ["h", "hh", "ahh", "0h", "zhh"]
+      .iter()
+      .map(|s| s.to_string()),
+  );
+  let keys: Vec<BalancedKey<Base36>> = names.iter().map(|s| s.parse()).collect::<Result<_, _>>()?;
+  let bytes: Vec<Vec<u8>> = keys.iter().map(|k| k.to_ordered_bytes()).collect();
+  for (x, bx) in keys.iter().zip(&bytes) {
+    assert_eq!(&BalancedKey::<Base36>::from_ordered_bytes(bx)?, x);
+    for (y, by) in keys.iter().zip(&bytes) {
+      assert_eq!(x.cmp(y), bx.cmp(by), "{} {}", x, y);
+    }
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn lexicon_order_preserved(a in "[+\\-/0-9A-Za-z]{0,8}", b in "[+\\-/0-9A-Za-z]{0,8}") {
+    let (a, b) = (LexiconKey::new(&a).unwrap(), LexiconKey::new(&b).unwrap());
+    let (ba, bb) = (a.to_ordered_bytes(), b.to_ordered_bytes());
+    prop_assert_eq!(a.cmp(&b), ba.cmp(&bb));
+    prop_assert_eq!(LexiconKey::from_ordered_bytes(&ba).unwrap(), a);
+  }
+
+  #[test]
+  fn balanced_order_preserved(a in "[+\\-/0-9A-Za-z]{0,8}", b in "[+\\-/0-9A-Za-z]{0,8}") {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    let (ba, bb) = (a.to_ordered_bytes(), b.to_ordered_bytes());
+    prop_assert_eq!(a.cmp(&b), ba.cmp(&bb));
+    prop_assert_eq!(BalancedKey::from_ordered_bytes(&ba).unwrap(), a);
+  }
+
+  #[test]
+  fn balanced_middle_runs(a in "[STU]{0,6}", b in "[STU]{0,6}") {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    prop_assert_eq!(a.cmp(&b), a.to_ordered_bytes().cmp(&b.to_ordered_bytes()));
+  }
+}
+
+#[test]
+fn format_of_bytes() -> Result<(), BisectionKeyError> {
+  assert_eq!(
+    LexiconKey::new("+a")?.to_ordered_bytes(),
+    vec![ORDERED_BYTES_VERSION, 1, 40]
+  );
+  // `T` is middle, `S` is below, `U` is above, terminated by `34`
+  assert_eq!(
+    BalancedKey::new("TS")?.to_ordered_bytes(),
+    vec![1, 33, 32, 34]
+  );
+  assert_eq!(
+    BalancedKey::new("TU")?.to_ordered_bytes(),
+    vec![1, 35, 36, 34]
+  );
+  assert_eq!(BalancedKey::new("TT")?.to_ordered_bytes(), vec![1, 34]);
+  Ok(())
+}
+
+#[test]
+fn malformed_bytes() {
+  assert_eq!(
+    LexiconKey::<Base65>::from_ordered_bytes(&[]),
+    Err(BisectionKeyError::InvalidBytes { position: 0 })
+  );
+  assert_eq!(
+    LexiconKey::<Base65>::from_ordered_bytes(&[9, 1]),
+    Err(BisectionKeyError::UnsupportedVersion { version: 9 })
+  );
+  assert_eq!(
+    LexiconKey::<Base65>::from_ordered_bytes(&[1, 1, 0]),
+    Err(BisectionKeyError::InvalidBytes { position: 2 })
+  );
+  // missing terminator
+  assert!(BalancedKey::<Base65>::from_ordered_bytes(&[1, 40]).is_err());
+  // middle digit promised to go up, but goes down
+  assert!(BalancedKey::<Base65>::from_ordered_bytes(&[1, 35, 32, 34]).is_err());
+  // trailing middle digit
+  assert!(BalancedKey::<Base65>::from_ordered_bytes(&[1, 40, 33, 34]).is_err());
+}
@@ -0,0 +1,151 @@
+extern crate bisection_key;
+
+use std::collections::BTreeMap;
+
+use bisection_key::{
+  BalancedKey, BisectionKeyError, Change, FractionalKey, LexiconKey, OrderedList,
+};
+
+fn values<K: FractionalKey>(list: &OrderedList<K, char>) -> String {
+  list.values().collect()
+}
+
+/// applies changes to a copy of the storage, which should end up the same as the list
+fn apply<K: FractionalKey>(rows: &mut BTreeMap<K, char>, changes: Vec<Change<K, char>>) {
+  for change in changes {
+    match change {
+      Change::Insert(k, v) => {
+        rows.insert(k, v);
+      }
+      Change::Remove(k) => {
+        rows.remove(&k);
+      }
+    }
+  }
+}
+
+fn editing<K: FractionalKey>() -> Result<(), BisectionKeyError> {
+  let mut rows: BTreeMap<K, char> = BTreeMap::new();
+  let mut list: OrderedList<K, char> = OrderedList::new();
+
+  let c = list.push_back('c')?;
+  let a = list.push_front('a')?;
+  list.insert_before(&c, 'b')?;
+  let e = list.insert_after(&c, 'e')?;
+  list.insert_at(3, 'd')?;
+  list.insert_at(5, 'f')?;
+  assert_eq!(values(&list), "abcdef");
+  apply(&mut rows, list.take_changes());
+  assert!(list.changes().is_empty());
+
+  let a = list.move_after(&a, &c)?;
+  assert_eq!(values(&list), "bcadef");
+  let e = list.move_before(&e, &list.first().unwrap().0.to_owned())?;
+  assert_eq!(values(&list), "ebcadf");
+  let a = list.move_to(&a, 5)?;
+  assert_eq!(values(&list), "ebcdfa");
+  assert_eq!(list.index_of(&a), Some(5));
+  assert!(list.get_index(0) == Some((&e, &'e')));
+
+  assert_eq!(list.set(&a, 'A')?, 'a');
+  assert_eq!(list.remove(&c), Some('c'));
+  assert_eq!(list.remove(&c), None);
+  assert_eq!(values(&list), "ebdfA");
+  apply(&mut rows, list.take_changes());
+
+  let stored: Vec<(K, char)> = rows.into_iter().collect();
+  let listed: Vec<(K, char)> = list.iter().map(|(k, v)| (k.to_owned(), *v)).collect();
+  assert!(stored == listed);
+
+  Ok(())
+}
+
+#[test]
+fn test_editing() -> Result<(), BisectionKeyError> {
+  editing::<LexiconKey>()?;
+  editing::<BalancedKey>()
+}
+
+#[test]
+fn test_moving_in_place() -> Result<(), BisectionKeyError> {
+  let mut list: OrderedList<LexiconKey, char> = OrderedList::from_values("abc".chars())?;
+  assert_eq!(list.take_changes().len(), 3);
+  let keys: Vec<LexiconKey> = list.keys().cloned().collect();
+
+  // moves that keep the order don't change keys
+  assert_eq!(list.move_before(&keys[0], &keys[1])?, keys[0]);
+  assert_eq!(list.move_after(&keys[2], &keys[1])?, keys[2]);
+  assert_eq!(list.move_to(&keys[1], 1)?, keys[1]);
+  assert!(list.changes().is_empty());
+
+  list.move_before(&keys[2], &keys[0])?;
+  assert_eq!(values(&list), "cab");
+  assert!(matches!(list.changes(), [Change::Remove(k), Change::Insert(_, 'c')] if *k == keys[2]));
+
+  Ok(())
+}
+
+#[test]
+fn test_errors() -> Result<(), BisectionKeyError> {
+  let mut list: OrderedList<LexiconKey, char> = OrderedList::new();
+  let a = list.push_back('a')?;
+  let missing = LexiconKey::new("missing")?;
+  assert!(matches!(
+    list.insert_after(&missing, 'b'),
+    Err(BisectionKeyError::KeyNotFound { .. })
+  ));
+  assert!(matches!(
+    list.move_before(&a, &missing),
+    Err(BisectionKeyError::KeyNotFound { .. })
+  ));
+  assert!(matches!(
+    list.set(&missing, 'b'),
+    Err(BisectionKeyError::KeyNotFound { .. })
+  ));
+  assert_eq!(
+    list.insert_at(2, 'b'),
+    Err(BisectionKeyError::IndexOutOfRange { index: 2, len: 1 })
+  );
+  assert_eq!(
+    list.move_to(&a, 1),
+    Err(BisectionKeyError::IndexOutOfRange { index: 1, len: 1 })
+  );
+  assert_eq!(list.len(), 1);
+
+  Ok(())
+}
+
+#[test]
+fn test_loading() -> Result<(), BisectionKeyError> {
+  let rows = [("a", 'x'), ("T", 'y'), ("b", 'z')];
+  let mut list: OrderedList<LexiconKey, char> = rows
+    .iter()
+    .map(|(k, v)| Ok((LexiconKey::new(k)?, *v)))
+    .collect::<Result<_, BisectionKeyError>>()?;
+  assert_eq!(values(&list), "yxz");
+  assert!(list.changes().is_empty());
+
+  list.push_back('w')?;
+  assert_eq!(list.changes().len(), 1);
+
+  Ok(())
+}
+
+/// a value that can't be cloned
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+#[test]
+fn test_removing_without_clone() -> Result<(), BisectionKeyError> {
+  let mut list: OrderedList<LexiconKey, Handle> = [("a", 1), ("b", 2)]
+    .iter()
+    .map(|(k, v)| Ok((LexiconKey::new(k)?, Handle(*v))))
+    .collect::<Result<_, BisectionKeyError>>()?;
+  let a = LexiconKey::new("a")?;
+  assert_eq!(list.remove(&a), Some(Handle(1)));
+  assert_eq!(list.remove(&a), None);
+  assert_eq!(list.len(), 1);
+  assert!(matches!(list.changes(), [Change::Remove(k)] if *k == a));
+
+  Ok(())
+}
@@ -0,0 +1,103 @@
+extern crate bisection_key;
+
+use bisection_key::{BalancedKey, Base36, BisectionKeyError, Charset, LexiconKey, PackedKey};
+use proptest::prelude::*;
+
+/// every key of `Base36` with at most 2 digits, including the empty key
+fn all_short_keys() -> Vec<String> {
+  let chars: Vec<char> = Base36::CHARS.chars().collect();
+  let mut xs = vec![String::new()];
+  for a in &chars {
+    xs.push(a.to_string());
+    for b in &chars {
+      xs.push(format!("{}{}", a, b));
+    }
+  }
+  xs
+}
+
+#[test]
+fn lexicon_packed_exhaustively() -> Result<(), BisectionKeyError> {
+  let keys: Vec<LexiconKey<Base36>> = all_short_keys()
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<_, _>>()?;
+  let packed: Vec<PackedKey<LexiconKey<Base36>>> = keys.iter().map(PackedKey::from).collect();
+  for (x, px) in keys.iter().zip(&packed) {
+    assert_eq!(&LexiconKey::from(px), x);
+    for (y, py) in keys.iter().zip(&packed) {
+      assert_eq!(x.cmp(y), px.cmp(py), "{} {}", x, y);
+    }
+  }
+  Ok(())
+}
+
+#[test]
+fn balanced_packed_exhaustively() -> Result<(), BisectionKeyError> {
+  let mut names = all_short_keys();
+  names.extend(
+    ["h", "hh", "ahh", "0h", "zhh"]
+      .iter()
+      .map(|s| s.to_string()),
+  );
+  let keys: Vec<BalancedKey<Base36>> = names.iter().map(|s| s.parse()).collect::<Result<_, _>>()?;
+  let packed: Vec<PackedKey<BalancedKey<Base36>>> = keys.iter().map(PackedKey::from).collect();
+  for (x, px) in keys.iter().zip(&packed) {
+    assert_eq!(&BalancedKey::from(px), x);
+    for (y, py) in keys.iter().zip(&packed) {
+      assert_eq!(x.cmp(y), px.cmp(py), "{} {}", x, y);
+    }
+  }
+  Ok(())
+}
+
+#[test]
+fn size_of_packed() -> Result<(), BisectionKeyError> {
+  // 7 bits per digit for the default charset
+  let k = LexiconKey::new("abcdefgh")?;
+  assert_eq!(PackedKey::from(&k).as_bytes().len(), 7);
+  // 6 bits per digit for `Base36`
+  let k: LexiconKey<Base36> = "abcd".parse()?;
+  assert_eq!(PackedKey::from(&k).as_bytes().len(), 3);
+  // balanced keys end with a terminator
+  let k = BalancedKey::new("abcdefg")?;
+  assert_eq!(PackedKey::from(&k).as_bytes().len(), 7);
+  assert_eq!(PackedKey::from(&k).to_string(), "abcdefg");
+  Ok(())
+}
+
+#[test]
+fn packed_inline() -> Result<(), BisectionKeyError> {
+  assert!(size_of::<PackedKey>() < size_of::<LexiconKey>());
+  assert!(size_of::<PackedKey<BalancedKey>>() < size_of::<BalancedKey>());
+  // 9 digits of 7 bits fit inline
+  let k = LexiconKey::new("aT3zzzzzV")?;
+  let packed = PackedKey::from(&k);
+  assert!(!packed.spilled());
+  assert_eq!(LexiconKey::from(&packed), k);
+  let k = LexiconKey::new("aT3zzzzzzzV")?;
+  let long = PackedKey::from(&k);
+  assert!(long.spilled());
+  assert_eq!(LexiconKey::from(&long), k);
+  assert!(packed < long);
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn lexicon_packed(a in "[+\\-/0-9A-Za-z]{0,24}", b in "[+\\-/0-9A-Za-z]{0,24}") {
+    let (a, b) = (LexiconKey::new(&a).unwrap(), LexiconKey::new(&b).unwrap());
+    let (pa, pb) = (PackedKey::from(&a), PackedKey::from(&b));
+    prop_assert_eq!(a.cmp(&b), pa.cmp(&pb));
+    prop_assert_eq!(LexiconKey::from(&pa), a);
+  }
+
+  #[test]
+  fn balanced_packed(a in "[+\\-/0-9A-Za-z]{0,24}", b in "[+\\-/0-9A-Za-z]{0,24}") {
+    let (a, b) = (BalancedKey::new(&a).unwrap(), BalancedKey::new(&b).unwrap());
+    let (pa, pb) = (PackedKey::from(&a), PackedKey::from(&b));
+    prop_assert_eq!(a.cmp(&b), pa.cmp(&pb));
+    prop_assert_eq!(a == b, pa == pb);
+    prop_assert_eq!(BalancedKey::from(&pa), a);
+  }
+}
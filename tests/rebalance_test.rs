@@ -0,0 +1,100 @@
+extern crate bisection_key;
+
+use bisection_key::{
+  rebalance, rebalance_window, BalancedKey, BisectionKeyError, FractionalKey, LexiconKey,
+};
+
+/// keys after many drag-and-drops towards the same neighbour
+fn grown_keys<K: FractionalKey>() -> Result<Vec<K>, BisectionKeyError> {
+  let mut xs: Vec<K> = K::n_between(None, None, 20)?;
+  for _ in 0..200 {
+    let key = K::between(Some(&xs[9]), Some(&xs[10]))?;
+    xs.insert(10, key);
+  }
+  Ok(xs)
+}
+
+fn assert_sorted<K: FractionalKey>(xs: &[K]) {
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+  }
+}
+
+fn check_rebalance<K: FractionalKey>() -> Result<(), BisectionKeyError> {
+  let xs = grown_keys::<K>()?;
+  assert!(xs.iter().any(|k| k.to_string().len() > 10));
+
+  let ys = rebalance(&xs)?;
+  assert_eq!(ys.len(), xs.len());
+  assert_sorted(&ys);
+  assert!(ys.iter().all(|k| k.to_string().len() <= 2));
+  Ok(())
+}
+
+fn check_window<K: FractionalKey>() -> Result<(), BisectionKeyError> {
+  let mut xs = grown_keys::<K>()?;
+  let changes = rebalance_window(&xs, 4)?;
+  assert!(!changes.is_empty());
+  // keys far away from the window are untouched
+  assert!(changes
+    .iter()
+    .all(|(idx, _)| *idx > 0 && *idx < xs.len() - 1));
+  for (idx, key) in changes {
+    xs[idx] = key;
+  }
+  assert_sorted(&xs);
+  assert!(xs.iter().all(|k| k.to_string().len() <= 4));
+
+  assert!(rebalance_window(&xs, 4)?.is_empty());
+  Ok(())
+}
+
+#[test]
+fn test_rebalance() -> Result<(), BisectionKeyError> {
+  check_rebalance::<LexiconKey>()?;
+  check_rebalance::<BalancedKey>()
+}
+
+#[test]
+fn test_rebalance_window() -> Result<(), BisectionKeyError> {
+  check_window::<LexiconKey>()?;
+  check_window::<BalancedKey>()
+}
+
+#[test]
+fn test_window_grows_over_tight_neighbours() -> Result<(), BisectionKeyError> {
+  // only long keys fit between `a` and `a+-`, so the window has to include neighbours
+  let mut xs: Vec<LexiconKey> = ["T", "a", "a+++++++++V", "a+-", "b"]
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<_, _>>()?;
+  let changes = rebalance_window(&xs, 3)?;
+  assert!(changes.len() > 1);
+  assert!(changes.iter().all(|(idx, _)| *idx != 0));
+  for (idx, key) in changes {
+    xs[idx] = key;
+  }
+  assert_sorted(&xs);
+  assert!(xs.iter().all(|k| k.to_string().len() <= 3));
+  Ok(())
+}
+
+#[test]
+fn test_window_grows_on_one_side() -> Result<(), BisectionKeyError> {
+  // nothing of 3 characters goes between `azz` and `b`, while there's room after `b`,
+  // so the window takes in `b` and leaves `azz` on the left untouched
+  let mut xs: Vec<LexiconKey> = ["T", "a", "azz", "azzzzzzV", "b", "c", "d"]
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<_, _>>()?;
+  let changes = rebalance_window(&xs, 3)?;
+  let indexes: Vec<usize> = changes.iter().map(|(idx, _)| *idx).collect();
+  assert_eq!(indexes, [3, 4]);
+  for (idx, key) in changes {
+    xs[idx] = key;
+  }
+  assert_sorted(&xs);
+  assert_eq!(xs[2].to_string(), "azz");
+  assert!(xs.iter().all(|k| k.to_string().len() <= 3));
+  Ok(())
+}
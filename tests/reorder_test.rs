@@ -0,0 +1,106 @@
+extern crate bisection_key;
+
+use std::collections::BTreeMap;
+
+use bisection_key::{reorder_keys, BalancedKey, BisectionKeyError, FractionalKey, LexiconKey};
+use proptest::prelude::*;
+
+/// keys after applying assignments, in the new order
+fn applied<K: FractionalKey>(
+  old: &[(usize, K)],
+  new_order: &[usize],
+  changes: &[(usize, K)],
+) -> Vec<K> {
+  let mut rows: BTreeMap<usize, K> = old.iter().cloned().collect();
+  rows.extend(changes.iter().cloned());
+  new_order.iter().map(|id| rows[id].to_owned()).collect()
+}
+
+fn assert_sorted<K: FractionalKey>(xs: &[K]) {
+  for pair in xs.windows(2) {
+    assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+  }
+}
+
+fn numbered<K: FractionalKey>(n: usize) -> Result<Vec<(usize, K)>, BisectionKeyError> {
+  Ok(
+    K::n_between(None, None, n)?
+      .into_iter()
+      .enumerate()
+      .collect(),
+  )
+}
+
+#[test]
+fn test_moving_one_item() -> Result<(), BisectionKeyError> {
+  let old: Vec<(usize, LexiconKey)> = numbered(6)?;
+  let new_order = [0, 1, 4, 2, 3, 5];
+  let changes = reorder_keys(&old, &new_order)?;
+  assert_eq!(changes.len(), 1);
+  assert_eq!(changes[0].0, 4);
+  assert_sorted(&applied(&old, &new_order, &changes));
+
+  // unchanged order rewrites nothing
+  let same: Vec<usize> = (0..6).collect();
+  assert!(reorder_keys(&old, &same)?.is_empty());
+
+  Ok(())
+}
+
+#[test]
+fn test_reversing() -> Result<(), BisectionKeyError> {
+  let old: Vec<(usize, BalancedKey)> = numbered(8)?;
+  let new_order: Vec<usize> = (0..8).rev().collect();
+  let changes = reorder_keys(&old, &new_order)?;
+  assert_eq!(changes.len(), 7);
+  assert_sorted(&applied(&old, &new_order, &changes));
+
+  Ok(())
+}
+
+#[test]
+fn test_added_and_removed_items() -> Result<(), BisectionKeyError> {
+  let old: Vec<(usize, LexiconKey)> = numbered(4)?;
+  // `1` is removed, `7` and `8` are new
+  let new_order = [7, 0, 2, 8, 3];
+  let changes = reorder_keys(&old, &new_order)?;
+  let ids: Vec<usize> = changes.iter().map(|(id, _)| *id).collect();
+  assert_eq!(ids, [7, 8]);
+  assert_sorted(&applied(&old, &new_order, &changes));
+
+  let empty: Vec<(usize, LexiconKey)> = vec![];
+  assert_eq!(reorder_keys(&empty, &[5, 6])?.len(), 2);
+  assert!(reorder_keys(&old, &[])?.is_empty());
+
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn reordered_keys_sorted(
+    order in (1..40usize).prop_flat_map(|n| Just((0..n).collect::<Vec<usize>>()).prop_shuffle()),
+  ) {
+    let old: Vec<(usize, LexiconKey)> = numbered(order.len()).unwrap();
+    let changes = reorder_keys(&old, &order).unwrap();
+    assert_sorted(&applied(&old, &order, &changes));
+
+    // kept items form an increasing run, and no longer run exists
+    let changed: Vec<usize> = changes.iter().map(|(id, _)| *id).collect();
+    let kept: Vec<usize> = order.iter().copied().filter(|id| !changed.contains(id)).collect();
+    prop_assert!(kept.windows(2).all(|w| w[0] < w[1]));
+    prop_assert_eq!(kept.len(), longest_increasing_len(&order));
+  }
+}
+
+/// length of a longest increasing subsequence, in `O(n^2)` for checking
+fn longest_increasing_len(xs: &[usize]) -> usize {
+  let mut lens = vec![1; xs.len()];
+  for i in 0..xs.len() {
+    for j in 0..i {
+      if xs[j] < xs[i] {
+        lens[i] = lens[i].max(lens[j] + 1);
+      }
+    }
+  }
+  lens.into_iter().max().unwrap_or(0)
+}
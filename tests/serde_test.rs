@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+extern crate bisection_key;
+
+use bisection_key::{BalancedKey, FractionalIndexKey, LexiconKey};
+
+#[test]
+fn json_round_trip() {
+  let k = LexiconKey::new("aV+z").unwrap();
+  let s = serde_json::to_string(&k).unwrap();
+  assert_eq!(s, "\"aV+z\"");
+  assert_eq!(serde_json::from_str::<LexiconKey>(&s).unwrap(), k);
+
+  let k = BalancedKey::new("aV+z").unwrap();
+  let s = serde_json::to_string(&k).unwrap();
+  assert_eq!(s, "\"aV+z\"");
+  assert_eq!(serde_json::from_str::<BalancedKey>(&s).unwrap(), k);
+
+  let k = FractionalIndexKey::new("a0V").unwrap();
+  let s = serde_json::to_string(&k).unwrap();
+  assert_eq!(s, "\"a0V\"");
+  assert_eq!(serde_json::from_str::<FractionalIndexKey>(&s).unwrap(), k);
+  assert!(serde_json::from_str::<FractionalIndexKey>("\"a00\"").is_err());
+
+  let xs = LexiconKey::n_between(None, None, 20).unwrap();
+  let s = serde_json::to_string(&xs).unwrap();
+  assert_eq!(serde_json::from_str::<Vec<LexiconKey>>(&s).unwrap(), xs);
+}
+
+#[test]
+fn msgpack_round_trip() {
+  let k = LexiconKey::new("Tz-").unwrap();
+  let bytes = rmp_serde::to_vec(&k).unwrap();
+  assert_eq!(rmp_serde::from_slice::<LexiconKey>(&bytes).unwrap(), k);
+
+  let k = BalancedKey::new("Tz-").unwrap();
+  let bytes = rmp_serde::to_vec(&k).unwrap();
+  assert_eq!(rmp_serde::from_slice::<BalancedKey>(&bytes).unwrap(), k);
+}
+
+#[test]
+fn malformed_keys() {
+  assert!(serde_json::from_str::<LexiconKey>("\"a b\"").is_err());
+  assert!(serde_json::from_str::<BalancedKey>("\"a_\"").is_err());
+  assert!(serde_json::from_str::<LexiconKey>("12").is_err());
+
+  let bytes = rmp_serde::to_vec(&"a!").unwrap();
+  assert!(rmp_serde::from_slice::<BalancedKey>(&bytes).is_err());
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "rusqlite")]
+
+extern crate bisection_key;
+
+use bisection_key::{
+  register_balanced_key_collation, BalancedKey, Base65, FractionalKey, LexiconKey,
+};
+use rusqlite::Connection;
+
+fn parse_all<K: FractionalKey>(xs: &[&str]) -> Vec<K> {
+  xs.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn lexicon_order() -> rusqlite::Result<()> {
+  let conn = Connection::open_in_memory()?;
+  conn.execute("CREATE TABLE lexicon_rows (key TEXT PRIMARY KEY)", ())?;
+  let mut keys: Vec<LexiconKey> = parse_all(&["a", "+", "aT", "a+V", "T", "zz", "-", "a0", "Z"]);
+  keys.extend(LexiconKey::n_between(None, None, 20).unwrap());
+  keys.sort();
+  keys.dedup();
+  for k in keys.iter().rev() {
+    conn.execute("INSERT INTO lexicon_rows (key) VALUES (?1)", [k])?;
+  }
+  let mut stmt = conn.prepare("SELECT key FROM lexicon_rows ORDER BY key")?;
+  let rows = stmt
+    .query_map((), |row| row.get::<_, LexiconKey>(0))?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+  assert_eq!(rows, keys);
+  Ok(())
+}
+
+#[test]
+fn balanced_order() -> rusqlite::Result<()> {
+  let conn = Connection::open_in_memory()?;
+  register_balanced_key_collation::<Base65>(&conn)?;
+  conn.execute(
+    "CREATE TABLE balanced_rows (key TEXT COLLATE BALANCED_KEY PRIMARY KEY)",
+    (),
+  )?;
+  // `aS` < `a` < `aU` in `BalancedKey`, unlike their text order
+  let mut keys: Vec<BalancedKey> = parse_all(&["a", "aS", "aU", "a+", "az", "T", "S", "U-", "+"]);
+  keys.extend(BalancedKey::n_between(None, None, 20).unwrap());
+  keys.sort();
+  keys.dedup();
+  for k in keys.iter().rev() {
+    conn.execute("INSERT INTO balanced_rows (key) VALUES (?1)", [k])?;
+  }
+  let mut stmt = conn.prepare("SELECT key FROM balanced_rows ORDER BY key")?;
+  let rows = stmt
+    .query_map((), |row| row.get::<_, BalancedKey>(0))?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+  assert_eq!(rows, keys);
+
+  // the collation is used for comparisons too
+  let (a, b): (BalancedKey, BalancedKey) = ("aS".parse().unwrap(), "a".parse().unwrap());
+  let smaller: i64 = conn.query_row(
+    "SELECT count(*) FROM balanced_rows WHERE key > ?1 AND key < ?2",
+    [&a, &b],
+    |row| row.get(0),
+  )?;
+  assert_eq!(smaller, 0);
+
+  // `aT` equals `a`, so it's a duplicate of the primary key
+  let duplicated = conn.execute(
+    "INSERT INTO balanced_rows (key) VALUES (?1)",
+    [BalancedKey::new("aT").unwrap()],
+  );
+  assert!(duplicated.is_err());
+  Ok(())
+}
+
+#[test]
+fn collation_in_order_by() -> rusqlite::Result<()> {
+  let conn = Connection::open_in_memory()?;
+  register_balanced_key_collation::<Base65>(&conn)?;
+  conn.execute("CREATE TABLE plain_rows (key TEXT)", ())?;
+  for k in ["aU", "a", "not a key", "aS", "T"] {
+    conn.execute("INSERT INTO plain_rows (key) VALUES (?1)", [k])?;
+  }
+  let mut stmt = conn.prepare("SELECT key FROM plain_rows ORDER BY key COLLATE BALANCED_KEY")?;
+  let rows = stmt
+    .query_map((), |row| row.get::<_, String>(0))?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+  // texts that are not keys go last
+  assert_eq!(rows, ["T", "aS", "a", "aU", "not a key"]);
+  Ok(())
+}
+
+#[test]
+fn invalid_values() -> rusqlite::Result<()> {
+  let conn = Connection::open_in_memory()?;
+  let text = conn.query_row("SELECT 'a b'", (), |row| row.get::<_, LexiconKey>(0));
+  assert!(matches!(
+    text,
+    Err(rusqlite::Error::FromSqlConversionFailure(..))
+  ));
+  let number = conn.query_row("SELECT 42", (), |row| row.get::<_, BalancedKey>(0));
+  assert!(matches!(
+    number,
+    Err(rusqlite::Error::InvalidColumnType(..))
+  ));
+  Ok(())
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "sqlx")]
+
+extern crate bisection_key;
+
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use bisection_key::{BalancedKey, FractionalKey, LexiconKey};
+use sqlx::{Connection, PgConnection};
+
+/// a database from `DATABASE_URL`, or a throwaway cluster started with `initdb` and `pg_ctl`
+/// found in `PATH`, stopped and removed when dropped
+struct TestDatabase {
+  url: String,
+  dir: Option<PathBuf>,
+}
+
+impl TestDatabase {
+  fn start() -> Option<Self> {
+    if let Ok(url) = std::env::var("DATABASE_URL") {
+      return Some(Self { url, dir: None });
+    }
+    let dir = std::env::temp_dir().join(format!("bisection-key-pg-{}", process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let initialized = Command::new("initdb")
+      .args(["-U", "postgres", "--auth=trust", "-D"])
+      .arg(&dir)
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .status();
+    if !initialized.is_ok_and(|s| s.success()) {
+      // postgres is not installed, or refuses to run as root
+      let _ = std::fs::remove_dir_all(&dir);
+      return None;
+    }
+    let port = TcpListener::bind("127.0.0.1:0")
+      .ok()?
+      .local_addr()
+      .ok()?
+      .port();
+    let options = format!(
+      "-p {} -k {} -c listen_addresses=127.0.0.1",
+      port,
+      dir.display()
+    );
+    let started = Command::new("pg_ctl")
+      .args(["-w", "-o", &options, "-l"])
+      .arg(dir.join("log"))
+      .arg("-D")
+      .arg(&dir)
+      .arg("start")
+      .stdout(Stdio::null())
+      .status();
+    let db = Self {
+      url: format!("postgres://postgres@127.0.0.1:{}/postgres", port),
+      dir: Some(dir),
+    };
+    if started.is_ok_and(|s| s.success()) {
+      Some(db)
+    } else {
+      None
+    }
+  }
+}
+
+impl Drop for TestDatabase {
+  fn drop(&mut self) {
+    if let Some(dir) = &self.dir {
+      let _ = Command::new("pg_ctl")
+        .args(["-w", "-m", "immediate", "-D"])
+        .arg(dir)
+        .arg("stop")
+        .stdout(Stdio::null())
+        .status();
+      let _ = std::fs::remove_dir_all(dir);
+    }
+  }
+}
+
+fn parse_all<K: FractionalKey>(xs: &[&str]) -> Vec<K> {
+  xs.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+async fn lexicon_order(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+  sqlx::query(r#"CREATE TEMP TABLE lexicon_rows (key TEXT COLLATE "C" PRIMARY KEY)"#)
+    .execute(&mut *conn)
+    .await?;
+  let mut keys: Vec<LexiconKey> = parse_all(&["a", "+", "aT", "a+V", "T", "zz", "-", "a0", "Z"]);
+  keys.extend(LexiconKey::n_between(None, None, 20).unwrap());
+  keys.sort();
+  keys.dedup();
+  for k in keys.iter().rev() {
+    sqlx::query("INSERT INTO lexicon_rows (key) VALUES ($1)")
+      .bind(k)
+      .execute(&mut *conn)
+      .await?;
+  }
+  let rows: Vec<(LexiconKey,)> = sqlx::query_as("SELECT key FROM lexicon_rows ORDER BY key")
+    .fetch_all(&mut *conn)
+    .await?;
+  assert_eq!(rows.into_iter().map(|(k,)| k).collect::<Vec<_>>(), keys);
+
+  // keys are bound as arrays of text as well
+  let picked: Vec<LexiconKey> = parse_all(&["a", "T", "missing"]);
+  let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM lexicon_rows WHERE key = ANY($1)")
+    .bind(&picked)
+    .fetch_one(&mut *conn)
+    .await?;
+  assert_eq!(count, 2);
+  Ok(())
+}
+
+async fn balanced_order(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+  sqlx::query("CREATE TEMP TABLE balanced_rows (key BYTEA PRIMARY KEY)")
+    .execute(&mut *conn)
+    .await?;
+  // `aS` < `a` < `aU` in `BalancedKey`, unlike their text order
+  let mut keys: Vec<BalancedKey> = parse_all(&["a", "aS", "aU", "a+", "az", "T", "S", "U-", "+"]);
+  keys.extend(BalancedKey::n_between(None, None, 20).unwrap());
+  keys.sort();
+  keys.dedup();
+  for k in keys.iter().rev() {
+    sqlx::query("INSERT INTO balanced_rows (key) VALUES ($1)")
+      .bind(k)
+      .execute(&mut *conn)
+      .await?;
+  }
+  let rows: Vec<(BalancedKey,)> = sqlx::query_as("SELECT key FROM balanced_rows ORDER BY key")
+    .fetch_all(&mut *conn)
+    .await?;
+  assert_eq!(rows.into_iter().map(|(k,)| k).collect::<Vec<_>>(), keys);
+
+  // `aT` equals `a`, so it's a duplicate of the primary key
+  let duplicated = sqlx::query("INSERT INTO balanced_rows (key) VALUES ($1)")
+    .bind(BalancedKey::new("aT").unwrap())
+    .execute(&mut *conn)
+    .await;
+  assert!(duplicated.is_err());
+  Ok(())
+}
+
+async fn invalid_values(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+  let text = sqlx::query_as::<_, (LexiconKey,)>("SELECT 'a b'::text")
+    .fetch_one(&mut *conn)
+    .await;
+  assert!(matches!(text, Err(sqlx::Error::ColumnDecode { .. })));
+  let bytes = sqlx::query_as::<_, (BalancedKey,)>("SELECT '\\x0200'::bytea")
+    .fetch_one(&mut *conn)
+    .await;
+  assert!(matches!(bytes, Err(sqlx::Error::ColumnDecode { .. })));
+  Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs Postgres, run with `--ignored`"]
+async fn postgres_columns() -> Result<(), sqlx::Error> {
+  let db = TestDatabase::start().expect("set DATABASE_URL or put initdb and pg_ctl in PATH");
+  let mut conn = PgConnection::connect(&db.url).await?;
+  lexicon_order(&mut conn).await?;
+  balanced_order(&mut conn).await?;
+  invalid_values(&mut conn).await?;
+  conn.close().await
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "wasm")]
+
+//! Parity of the `wasm` bindings with native keys, on a fixed corpus.
+//! Runs natively, and under a wasm runtime with:
+//!
+//! ```text
+//! CARGO_TARGET_WASM32_WASIP1_RUNNER=wasmtime cargo test --target wasm32-wasip1 --features wasm --test wasm_test
+//! ```
+
+extern crate bisection_key;
+
+use bisection_key::wasm;
+use bisection_key::LexiconKey;
+
+/// `operation`, inputs and the expected output separated by tabs, errors start with `!`
+const CORPUS: &str = include_str!("wasm_corpus.txt");
+
+fn native(op: &str, args: &[&str]) -> Result<String, String> {
+  let parse = |s: &str| LexiconKey::new(s).map_err(|e| e.to_string());
+  let key = match op {
+    "bisect" => parse(args[0])?.bisect(&parse(args[1])?),
+    "bisect_end" => parse(args[0])?.bisect_end(),
+    "bisect_beginning" => parse(args[0])?.bisect_beginning(),
+    "compare" => {
+      let ordering = parse(args[0])?.cmp(&parse(args[1])?);
+      return Ok((ordering as i32).to_string());
+    }
+    _ => panic!("unknown operation: {}", op),
+  };
+  key.map(|k| k.to_string()).map_err(|e| e.to_string())
+}
+
+fn binding(op: &str, args: &[&str]) -> Result<String, String> {
+  match op {
+    "bisect" => wasm::bisect(args[0], args[1]),
+    "bisect_end" => wasm::bisect_end(args[0]),
+    "bisect_beginning" => wasm::bisect_beginning(args[0]),
+    "compare" => wasm::compare(args[0], args[1]).map(|x| x.to_string()),
+    _ => panic!("unknown operation: {}", op),
+  }
+}
+
+#[test]
+fn parity_with_corpus() {
+  for line in CORPUS.lines() {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let (op, args, expected) = (
+      fields[0],
+      &fields[1..fields.len() - 1],
+      fields[fields.len() - 1],
+    );
+    let expected = match expected.strip_prefix('!') {
+      Some(message) => Err(message.to_owned()),
+      None => Ok(expected.to_owned()),
+    };
+    assert_eq!(binding(op, args), expected, "{}", line);
+    assert_eq!(native(op, args), expected, "{}", line);
+  }
+}